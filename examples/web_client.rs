@@ -1,18 +1,17 @@
-use clap::Parser;
-use image::{ImageBuffer, Rgb, RgbImage};
+use clap::{Parser, ValueEnum};
+use inky_el133::fit::{self, Fit, Focus, Resize};
+use inky_el133::{Canvas, Color, Palette, Preview};
 use std::path::PathBuf;
 
-const WIDTH: u32 = 1600;
-const HEIGHT: u32 = 1200;
-
-const DISPLAY_COLORS: [[u8; 3]; 6] = [
-    [0, 0, 0],       // Black
-    [255, 255, 255], // White
-    [255, 255, 0],   // Yellow
-    [255, 0, 0],     // Red
-    [0, 0, 255],     // Blue
-    [0, 255, 0],     // Green
-];
+#[derive(Clone, Copy, ValueEnum)]
+enum FitMode {
+    /// Letterbox the whole image onto white
+    Contain,
+    /// Fill the panel, cropping around the centre
+    Cover,
+    /// Stretch to the panel's aspect ratio
+    Stretch,
+}
 
 #[derive(Parser)]
 #[command(about = "Process and send images to Inky display server")]
@@ -23,19 +22,50 @@ struct Args {
     /// Server URL (default: http://localhost:3000)
     #[arg(short, long, default_value = "http://localhost:3000")]
     server: String,
+
+    /// How to fit the image to the panel
+    #[arg(short, long, value_enum, default_value = "contain")]
+    fit: FitMode,
+
+    /// Fit for a panel mounted in portrait
+    #[arg(long)]
+    portrait: bool,
+
+    /// Save a preview of how the panel will look to this path instead of sending
+    #[arg(short, long)]
+    preview: Option<PathBuf>,
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
 
     println!("Loading image: {}", args.input.display());
-    let img = image::open(&args.input)?;
+    let img = fit::open(&args.input)?;
 
-    println!("Resizing to {}x{}...", WIDTH, HEIGHT);
-    let resized = img.resize_exact(WIDTH, HEIGHT, image::imageops::FilterType::Lanczos3);
+    println!("Fitting to the panel...");
+    let mode = match args.fit {
+        FitMode::Contain => Fit::Contain(Color::White),
+        FitMode::Cover => Fit::Cover(Focus::CENTER),
+        FitMode::Stretch => Fit::Stretch,
+    };
+    let resize = Resize::new(mode).portrait(args.portrait);
 
     println!("Quantizing colors to 6-color palette...");
-    let processed = quantize_image(resized.to_rgb8());
+    let palette = Palette::default();
+    let mut canvas = Canvas::new();
+    canvas.load_resized(&img, &resize, &palette)?;
+
+    if let Some(path) = args.preview {
+        println!("Saving preview to {}...", path.display());
+        Preview::new(palette)
+            .scale(2)
+            .grid(true)
+            .render_image(&canvas)
+            .save(&path)?;
+        return Ok(());
+    }
+
+    let processed = canvas.to_rgb_image(&palette);
 
     println!("Encoding PNG...");
     let mut png_data = Vec::new();
@@ -59,24 +89,3 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
-
-fn quantize_image(img: RgbImage) -> RgbImage {
-    ImageBuffer::from_fn(WIDTH, HEIGHT, |x, y| {
-        let pixel = img.get_pixel(x, y);
-        let rgb = [pixel[0], pixel[1], pixel[2]];
-        let closest = find_closest_color(rgb);
-        Rgb(closest)
-    })
-}
-
-fn find_closest_color(rgb: [u8; 3]) -> [u8; 3] {
-    *DISPLAY_COLORS
-        .iter()
-        .min_by_key(|color| {
-            let dr = (rgb[0] as i32 - color[0] as i32).abs();
-            let dg = (rgb[1] as i32 - color[1] as i32).abs();
-            let db = (rgb[2] as i32 - color[2] as i32).abs();
-            dr + dg + db
-        })
-        .unwrap()
-}
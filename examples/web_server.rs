@@ -1,40 +1,18 @@
 use axum::{Router, extract::State, http::StatusCode, response::IntoResponse, routing::post};
-use inky_el133::{InkyDisplay, colors};
-use std::sync::{Arc, Mutex};
-
-const WIDTH: u32 = 1600;
-const HEIGHT: u32 = 1200;
-
-const COLOR_MAP: [([u8; 3], u8); 6] = [
-    ([0, 0, 0], colors::BLACK),
-    ([255, 255, 255], colors::WHITE),
-    ([255, 255, 0], colors::YELLOW),
-    ([255, 0, 0], colors::RED),
-    ([0, 0, 255], colors::BLUE),
-    ([0, 255, 0], colors::GREEN),
-];
+use inky_el133::fit::{self, Fit, Resize};
+use inky_el133::{Canvas, Color, InkyDisplay, InkyError, Palette};
+use std::sync::Arc;
+use tokio::sync::Mutex;
 
 struct AppState {
     display: Mutex<InkyDisplay>,
 }
 
-fn map_color(rgb: [u8; 3]) -> u8 {
-    COLOR_MAP
-        .iter()
-        .min_by_key(|(color_rgb, _)| {
-            let dr = (rgb[0] as i32 - color_rgb[0] as i32).abs();
-            let dg = (rgb[1] as i32 - color_rgb[1] as i32).abs();
-            let db = (rgb[2] as i32 - color_rgb[2] as i32).abs();
-            dr + dg + db
-        })
-        .map(|(_, idx)| *idx)
-        .unwrap_or(colors::WHITE)
-}
-
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    println!("Initializing Inky Impression display...");
-    let display = InkyDisplay::new()?;
+    // Leave the current image alone; the panel is initialized on the first update
+    println!("Opening Inky Impression display...");
+    let display = InkyDisplay::open()?;
 
     let state = Arc::new(AppState {
         display: Mutex::new(display),
@@ -46,7 +24,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await?;
     println!("Server listening on http://0.0.0.0:3000");
-    println!("POST a PNG image to /display (1600x1200, indexed/palette mode)");
+    println!("POST an image to /display (any size; letterboxed onto white)");
 
     axum::serve(listener, app).await?;
 
@@ -57,57 +35,39 @@ async fn update_display(
     State(state): State<Arc<AppState>>,
     body: axum::body::Bytes,
 ) -> impl IntoResponse {
-    // Decode PNG
-    let img = match image::load_from_memory(&body) {
-        Ok(img) => img,
-        Err(e) => {
+    // Decode, fit and map to panel colors before taking the display lock.
+    // This takes a while for large photos, so keep it off the async workers.
+    let loaded = tokio::task::spawn_blocking(move || {
+        let resize = Resize::new(Fit::Contain(Color::White));
+        let mut canvas = Canvas::new();
+        canvas.load_resized(&fit::decode(&body)?, &resize, &Palette::default())?;
+        Ok::<_, InkyError>(canvas)
+    })
+    .await;
+    let canvas = match loaded {
+        Ok(Ok(canvas)) => canvas,
+        Ok(Err(e)) => {
             return (
                 StatusCode::BAD_REQUEST,
-                format!("Failed to decode image: {}", e),
+                format!("Failed to load image: {}", e),
             );
         }
-    };
-
-    // Validate dimensions
-    if img.width() != WIDTH || img.height() != HEIGHT {
-        return (
-            StatusCode::BAD_REQUEST,
-            format!(
-                "Invalid dimensions: expected {}x{}, got {}x{}",
-                WIDTH,
-                HEIGHT,
-                img.width(),
-                img.height()
-            ),
-        );
-    }
-
-    let rgb_img = img.to_rgb8();
-    let mut display = match state.display.lock() {
-        Ok(d) => d,
         Err(e) => {
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Failed to acquire display lock: {}", e),
+                format!("Image loading failed: {}", e),
             );
         }
     };
 
-    for y in 0..HEIGHT {
-        for x in 0..WIDTH {
-            let pixel = rgb_img.get_pixel(x, y);
-            let color = map_color([pixel[0], pixel[1], pixel[2]]);
-            if let Err(e) = display.set_pixel(x as usize, y as usize, color) {
-                return (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    format!("Failed to set pixel: {}", e),
-                );
-            }
-        }
-    }
+    let mut display = state.display.lock().await;
+    *display.canvas_mut() = canvas;
 
     println!("Updating display (~32 seconds)...");
-    if let Err(e) = display.show() {
+    if let Err(e) = display
+        .show_async(|progress| println!("  {:?}", progress))
+        .await
+    {
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             format!("Failed to update display: {}", e),
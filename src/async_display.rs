@@ -0,0 +1,196 @@
+//! Tokio-based variant of [`InkyDisplay::show`].
+//!
+//! Frame data is still written synchronously (about half a second per
+//! controller at 10 MHz), but the reset pulse, command delays and the BUSY
+//! waits, which make up nearly all of a refresh, sleep on the Tokio timer
+//! instead of blocking the thread.
+
+use core::time::Duration;
+
+use tokio::time::{Instant, sleep};
+
+use crate::canvas::PackedFrame;
+use crate::constants::*;
+use crate::error::Result;
+use crate::timing::Phase;
+use crate::transport::{ChipSelect, Transport};
+use crate::{INIT_SEQUENCE, InkyDisplay, POWER_OFF, REFRESH, REFRESH_STEPS, Step};
+
+/// Interval between BUSY samples while waiting asynchronously
+const BUSY_POLL: Duration = Duration::from_millis(20);
+
+/// Refresh milestones reported by [`InkyDisplay::show_async`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Progress {
+    /// Frame data for CS0 has been sent
+    Cs0Transferred,
+    /// Frame data for CS1 has been sent
+    Cs1Transferred,
+    /// The panel has powered on
+    PoweredOn,
+    /// The refresh has started; this phase takes about 32 seconds
+    Refreshing,
+    /// The refresh is done and the panel has powered off
+    PoweredOff,
+}
+
+impl<T: Transport> InkyDisplay<T> {
+    /// Update the display without blocking the async runtime
+    ///
+    /// Behaves like [`show`](Self::show), calling `on_progress` as each stage
+    /// completes. If a step fails the panel is powered off before the error is
+    /// returned, or the power-off error is returned if that fails too. A
+    /// future dropped mid-refresh cannot do that; the panel then stays
+    /// powered until the next refresh completes or the display is dropped.
+    ///
+    /// # Example
+    /// ```
+    /// use inky_el133::{InkyDisplay, Progress};
+    /// use inky_el133::transport::RecordingTransport;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> inky_el133::Result<()> {
+    /// let mut display = InkyDisplay::with_transport(RecordingTransport::new())?;
+    ///
+    /// let mut seen = Vec::new();
+    /// display.show_async(|progress| seen.push(progress)).await?;
+    /// assert_eq!(seen.first(), Some(&Progress::Cs0Transferred));
+    /// assert_eq!(seen.last(), Some(&Progress::PoweredOff));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn show_async<F>(&mut self, mut on_progress: F) -> Result<()>
+    where
+        F: FnMut(Progress),
+    {
+        let result = self.run_show_async(&mut on_progress).await;
+        if result.is_err() {
+            self.ensure_powered_off_async(&mut on_progress).await?;
+        }
+        result
+    }
+
+    async fn run_show_async<F>(&mut self, on_progress: &mut F) -> Result<()>
+    where
+        F: FnMut(Progress),
+    {
+        let frame = self.take_frame(None)?;
+        let sent = self.send_frame_async(&frame, on_progress).await;
+        self.frame = frame;
+        sent?;
+
+        for step in REFRESH_STEPS {
+            self.run_step_async(step, on_progress).await?;
+        }
+        Ok(())
+    }
+
+    /// Send one refresh command and wait for BUSY to release, reporting
+    /// progress
+    async fn run_step_async<F>(&mut self, step: Step, on_progress: &mut F) -> Result<()>
+    where
+        F: FnMut(Progress),
+    {
+        self.start_step(step);
+        self.command_async(ChipSelect::Both, step.cmd, step.data)
+            .await?;
+        if step.phase == Phase::Refresh {
+            on_progress(Progress::Refreshing);
+        }
+
+        let waited = self
+            .wait_busy_async(self.timing.timeout_ms(step.phase))
+            .await?;
+        self.finish_step(step, waited)?;
+        match step.phase {
+            Phase::PowerOn => on_progress(Progress::PoweredOn),
+            Phase::PowerOff => on_progress(Progress::PoweredOff),
+            Phase::Init | Phase::Refresh => {}
+        }
+        Ok(())
+    }
+
+    /// Asynchronous counterpart of the power off after a failed refresh
+    async fn ensure_powered_off_async<F>(&mut self, on_progress: &mut F) -> Result<()>
+    where
+        F: FnMut(Progress),
+    {
+        if self.refreshing {
+            let waited = self.wait_busy_async(self.timing.refresh_timeout_ms).await?;
+            self.finish_step(REFRESH, waited)?;
+        }
+        if self.powered {
+            self.run_step_async(POWER_OFF, on_progress).await?;
+        }
+        Ok(())
+    }
+
+    /// Wake or initialize the panel if needed, then send both packed
+    /// halves, reporting each one
+    async fn send_frame_async<F>(&mut self, frame: &PackedFrame, on_progress: &mut F) -> Result<()>
+    where
+        F: FnMut(Progress),
+    {
+        if self.needs_init() {
+            self.initialize_async().await?;
+        }
+
+        self.command_async(ChipSelect::CS0, CMD_DTM, &frame.cs0)
+            .await?;
+        on_progress(Progress::Cs0Transferred);
+        self.command_async(ChipSelect::CS1, CMD_DTM, &frame.cs1)
+            .await?;
+        on_progress(Progress::Cs1Transferred);
+        Ok(())
+    }
+
+    /// Asynchronous counterpart of [`initialize`](Self::initialize), timing
+    /// the reset pulse on the Tokio timer
+    async fn initialize_async(&mut self) -> Result<()> {
+        let pulse = Duration::from_millis(RESET_PULSE_MS);
+
+        self.initialized = false;
+        let driven = !self.transport_mut().is_busy()?;
+        self.transport_mut().set_reset(true)?;
+        sleep(pulse).await;
+        self.transport_mut().set_reset(false)?;
+        sleep(pulse).await;
+        self.reset_done();
+
+        let waited = self.wait_busy_async(self.timing.init_timeout_ms).await?;
+        self.init_outcome(driven, waited)?;
+        for (cs, cmd, data) in INIT_SEQUENCE {
+            self.command_async(cs, cmd, data).await?;
+        }
+
+        self.initialized = true;
+        Ok(())
+    }
+
+    /// Send a command after sleeping off the per-command delay
+    async fn command_async(&mut self, cs: ChipSelect, cmd: u8, data: &[u8]) -> Result<()> {
+        if self.timing.command_delay_ms > 0 {
+            sleep(Duration::from_millis(self.timing.command_delay_ms)).await;
+        }
+        self.transport_mut().send_command(cs, cmd, data)
+    }
+
+    /// Poll BUSY on the Tokio timer until it releases or `timeout_ms` passes
+    ///
+    /// Returns how long BUSY was held, or `None` on timeout, like
+    /// [`Transport::wait_busy`].
+    async fn wait_busy_async(&mut self, timeout_ms: u64) -> Result<Option<Duration>> {
+        let timeout = Duration::from_millis(timeout_ms);
+        let start = Instant::now();
+
+        loop {
+            if !self.transport_mut().is_busy()? {
+                return Ok(Some(start.elapsed()));
+            }
+            if start.elapsed() >= timeout {
+                return Ok(None);
+            }
+            sleep(BUSY_POLL).await;
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use std::path::PathBuf;
+
+use crate::controller::{ControllerConfig, DisplayController};
+use crate::error::Result;
+use crate::{InkyDisplay, Orientation, Timing};
+
+/// Builder for an [`InkyDisplay`] with non-default wiring
+///
+/// Every setting defaults to the Inky Impression HAT on a Raspberry Pi, so
+/// only what differs on your board needs to be set.
+///
+/// # Example
+/// ```no_run
+/// use inky_el133::InkyDisplay;
+///
+/// // Second HAT on spidev1, GPIO lines on the Pi 5's gpiochip4
+/// let display = InkyDisplay::builder()
+///     .gpiochip("/dev/gpiochip4")
+///     .spidev("/dev/spidev1.0")
+///     .cs0_pin(18)
+///     .build()?;
+/// # Ok::<(), inky_el133::InkyError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InkyDisplayBuilder {
+    config: ControllerConfig,
+    timing: Timing,
+    strict: bool,
+    orientation: Orientation,
+}
+
+impl InkyDisplayBuilder {
+    /// Create a builder with the default wiring
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// GPIO character device holding the display lines (default `/dev/gpiochip0`)
+    pub fn gpiochip(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config.gpiochip = path.into();
+        self
+    }
+
+    /// SPI device the panel is attached to (default `/dev/spidev0.0`)
+    pub fn spidev(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config.spidev = path.into();
+        self
+    }
+
+    /// SPI clock in Hz (default 10 MHz)
+    pub fn spi_speed_hz(mut self, hz: u32) -> Self {
+        self.config.spi_speed_hz = hz;
+        self
+    }
+
+    /// Maximum bytes per SPI write (default 4096)
+    ///
+    /// Must not exceed the spidev `bufsiz` module parameter. Zero is rejected
+    /// by [`build`](Self::build) and [`open`](Self::open), before any device
+    /// is touched.
+    ///
+    /// # Example
+    /// ```
+    /// use inky_el133::{InkyDisplay, InkyError};
+    ///
+    /// let result = InkyDisplay::builder().chunk_size(0).build();
+    /// assert!(matches!(result, Err(InkyError::ZeroChunkSize)));
+    /// ```
+    pub fn chunk_size(mut self, size: usize) -> Self {
+        self.config.chunk_size = size;
+        self
+    }
+
+    /// GPIO line for chip select 0 (default 26)
+    pub fn cs0_pin(mut self, line: u32) -> Self {
+        self.config.cs0_pin = line;
+        self
+    }
+
+    /// GPIO line for chip select 1 (default 16)
+    pub fn cs1_pin(mut self, line: u32) -> Self {
+        self.config.cs1_pin = line;
+        self
+    }
+
+    /// GPIO line for data/command select (default 22)
+    pub fn dc_pin(mut self, line: u32) -> Self {
+        self.config.dc_pin = line;
+        self
+    }
+
+    /// GPIO line for reset (default 27)
+    pub fn reset_pin(mut self, line: u32) -> Self {
+        self.config.reset_pin = line;
+        self
+    }
+
+    /// GPIO line for the busy signal (default 17)
+    pub fn busy_pin(mut self, line: u32) -> Self {
+        self.config.busy_pin = line;
+        self
+    }
+
+    /// Command delays and busy timeouts (default [`Timing::fast`])
+    pub fn timing(mut self, timing: Timing) -> Self {
+        self.timing = timing;
+        self
+    }
+
+    /// Fail on busy-wait timeouts instead of warning (default off)
+    ///
+    /// With strict mode on, [`build`](Self::build) returns
+    /// [`InkyError::NotConnected`](crate::InkyError::NotConnected) when no
+    /// panel is attached. See [`InkyDisplay::set_strict`].
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Mounting of the panel (default [`Orientation::LANDSCAPE`])
+    ///
+    /// See [`InkyDisplay::set_orientation`].
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Settings collected so far
+    pub fn config(&self) -> &ControllerConfig {
+        &self.config
+    }
+
+    /// Open the hardware, reset the display and send the initialization sequence
+    pub fn build(self) -> Result<InkyDisplay> {
+        let mut display = InkyDisplay::from_parts(
+            DisplayController::open(&self.config)?,
+            self.timing,
+            self.strict,
+        )?;
+        display.set_orientation(self.orientation);
+        Ok(display)
+    }
+
+    /// Open the hardware without resetting or initializing the panel
+    ///
+    /// See [`InkyDisplay::open`].
+    pub fn open(self) -> Result<InkyDisplay> {
+        let mut display = InkyDisplay::attach_parts(
+            DisplayController::open(&self.config)?,
+            self.timing,
+            self.strict,
+        );
+        display.set_orientation(self.orientation);
+        Ok(display)
+    }
+}
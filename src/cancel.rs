@@ -0,0 +1,56 @@
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Flag for aborting a refresh started with
+/// [`InkyDisplay::show_cancellable`](crate::InkyDisplay::show_cancellable)
+///
+/// Share it between threads by reference or inside an `Arc`. Cancellation
+/// only takes effect at safe points: before any data is sent, before
+/// `CMD_DRF`, and while waiting for the refresh to finish. In every case the
+/// panel is powered off before the call returns; after `CMD_DRF` that means
+/// waiting for the refresh to finish first, as the controllers only accept
+/// `CMD_POF` once it has. Cancelling mid-refresh therefore saves no time; it
+/// only makes the call return
+/// [`InkyError::Cancelled`](crate::InkyError::Cancelled).
+///
+/// # Example
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use inky_el133::{CancellationToken, InkyDisplay, InkyError};
+/// use inky_el133::transport::RecordingTransport;
+///
+/// let cancel = CancellationToken::new();
+/// let mut display = InkyDisplay::with_transport(RecordingTransport::new())?;
+///
+/// cancel.cancel();
+/// assert!(matches!(display.show_cancellable(&cancel), Err(InkyError::Cancelled)));
+/// # }
+/// # Ok::<(), inky_el133::InkyError>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct CancellationToken {
+    cancelled: AtomicBool,
+}
+
+impl CancellationToken {
+    /// Create a token that has not been cancelled
+    pub const fn new() -> Self {
+        Self {
+            cancelled: AtomicBool::new(false),
+        }
+    }
+
+    /// Request cancellation of any refresh watching this token
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Whether [`cancel`](Self::cancel) has been called since the last reset
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Clear the cancellation request so the token can be reused
+    pub fn reset(&self) {
+        self.cancelled.store(false, Ordering::Release);
+    }
+}
@@ -0,0 +1,330 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::constants::*;
+use crate::error::{InkyError, Result};
+use crate::{Color, Orientation};
+
+/// Offscreen frame of [`Color`]s, 1600×1200 or 1200×1600 in portrait
+///
+/// A `Canvas` needs no hardware and builds on every platform, so frames can be
+/// rendered anywhere and handed to [`InkyDisplay::show_canvas`] on the device.
+/// Coordinates match [`InkyDisplay::set_pixel`]: `x` in 0..width, `y` in
+/// 0..height, origin top left.
+///
+/// [`InkyDisplay::show_canvas`]: crate::InkyDisplay::show_canvas
+/// [`InkyDisplay::set_pixel`]: crate::InkyDisplay::set_pixel
+///
+/// # Example
+/// ```
+/// use inky_el133::{Canvas, Color, colors};
+///
+/// let mut canvas = Canvas::new();
+/// canvas.set_pixel(10, 20, colors::RED)?;
+/// assert_eq!(canvas.get_pixel(10, 20), Some(colors::RED));
+/// assert_eq!(canvas.pixels().filter(|&(_, _, c)| c == Color::Red).count(), 1);
+/// # Ok::<(), inky_el133::InkyError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Canvas {
+    data: Vec<Color>,
+    width: usize,
+    height: usize,
+}
+
+/// Native rows and columns packed per block
+const BLOCK: usize = 64;
+
+/// Packed CS0/CS1 frame data, kept between frames so its buffers are reused
+#[derive(Debug, Default)]
+pub(crate) struct PackedFrame {
+    pub(crate) cs0: Vec<u8>,
+    pub(crate) cs1: Vec<u8>,
+}
+
+/// Logical frame that can be packed into a [`PackedFrame`]
+pub(crate) trait FrameSource {
+    /// Whether the frame has the logical size of `orientation`
+    fn fits(&self, orientation: Orientation) -> bool;
+
+    /// Rotate the frame into native panel order, split it at column 600 and
+    /// pack it into `frame`
+    fn rotate_and_split_into(&self, orientation: Orientation, frame: &mut PackedFrame);
+}
+
+impl Default for Canvas {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Canvas {
+    /// Create a 1600×1200 landscape canvas filled with white
+    pub fn new() -> Self {
+        Self::with_orientation(Orientation::LANDSCAPE)
+    }
+
+    /// Create a canvas filled with white, sized for `orientation`
+    pub fn with_orientation(orientation: Orientation) -> Self {
+        let (width, height) = (orientation.width(), orientation.height());
+        Self {
+            data: vec![Color::White; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Wrap pixels already laid out row-major at `width`×`height`
+    pub(crate) fn from_raw(data: Vec<Color>, width: usize, height: usize) -> Self {
+        debug_assert_eq!(data.len(), width * height);
+        Self {
+            data,
+            width,
+            height,
+        }
+    }
+
+    /// Width in pixels (1600, or 1200 in portrait)
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height in pixels (1200, or 1600 in portrait)
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Color at `(x, y)`, or `None` if out of bounds
+    pub fn get_pixel(&self, x: usize, y: usize) -> Option<Color> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        Some(self.data[y * self.width + x])
+    }
+
+    /// Set a single pixel
+    pub fn set_pixel(&mut self, x: usize, y: usize, color: Color) -> Result<()> {
+        if x >= self.width || y >= self.height {
+            return Err(InkyError::OutOfBounds(x, y));
+        }
+
+        self.data[y * self.width + x] = color;
+        Ok(())
+    }
+
+    /// Fill the entire canvas with a single color
+    pub fn fill(&mut self, color: Color) {
+        self.data.fill(color);
+    }
+
+    /// All pixels in row-major order
+    pub fn as_slice(&self) -> &[Color] {
+        &self.data
+    }
+
+    /// All pixels in row-major order, mutably
+    pub fn as_mut_slice(&mut self) -> &mut [Color] {
+        &mut self.data
+    }
+
+    /// Rows from top to bottom, each `width()` pixels long
+    pub fn rows(&self) -> impl Iterator<Item = &[Color]> {
+        self.data.chunks_exact(self.width)
+    }
+
+    /// Every pixel as `(x, y, color)` in row-major order
+    pub fn pixels(&self) -> impl Iterator<Item = (usize, usize, Color)> + '_ {
+        let width = self.width;
+        self.data
+            .iter()
+            .enumerate()
+            .map(move |(i, &color)| (i % width, i / width, color))
+    }
+}
+
+impl FrameSource for Canvas {
+    fn fits(&self, orientation: Orientation) -> bool {
+        (self.width, self.height) == (orientation.width(), orientation.height())
+    }
+
+    /// Packs in a single pass; see [`pack_frame`]
+    fn rotate_and_split_into(&self, orientation: Orientation, frame: &mut PackedFrame) {
+        debug_assert!(self.fits(orientation), "canvas size matches orientation");
+        pack_frame(orientation, frame, |index| self.data[index].index());
+    }
+}
+
+/// Pack a logical frame into `frame` in native order, reading the color
+/// index of each pixel from `pixel(y * width + x)`
+///
+/// The frame is mapped through `orientation` into the native 1200×1600
+/// frame. Native pixels are visited in square blocks so the strided reads of
+/// a rotation stay within cache, and pairs of pixels are packed straight into
+/// the CS0 and CS1 buffers.
+pub(crate) fn pack_frame<F>(orientation: Orientation, frame: &mut PackedFrame, pixel: F)
+where
+    F: Fn(usize) -> u8,
+{
+    frame.cs0.resize(HALF_BYTES, 0);
+    frame.cs1.resize(HALF_BYTES, 0);
+
+    // The logical index of a native pixel is affine in its row and column
+    let width = orientation.width();
+    let index = |(x, y): (usize, usize)| (y * width + x) as isize;
+    let origin = index(orientation.logical_position(0, 0));
+    let row_step = index(orientation.logical_position(1, 0)) - origin;
+    let col_step = index(orientation.logical_position(0, 1)) - origin;
+
+    for (out, first_col) in [(&mut frame.cs0, 0), (&mut frame.cs1, SPLIT_COL)] {
+        for block_row in (0..WIDTH).step_by(BLOCK) {
+            for block_col in (0..SPLIT_COL).step_by(BLOCK) {
+                let end_col = (block_col + BLOCK).min(SPLIT_COL);
+
+                for row in block_row..(block_row + BLOCK).min(WIDTH) {
+                    let mut src = origin
+                        + row as isize * row_step
+                        + (first_col + block_col) as isize * col_step;
+                    let start = (row * SPLIT_COL + block_col) / 2;
+                    let end = (row * SPLIT_COL + end_col) / 2;
+
+                    for byte in &mut out[start..end] {
+                        let first = pixel(src as usize);
+                        let second = pixel((src + col_step) as usize);
+                        *byte = (first << 4) | second;
+                        src += 2 * col_step;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rotation;
+
+    /// The original three-pass implementation for a landscape canvas: rotate
+    /// -90 degrees into a full-size buffer, split it into two and pack each
+    /// half.
+    fn reference(canvas: &Canvas) -> (Vec<u8>, Vec<u8>) {
+        assert!(canvas.fits(Orientation::LANDSCAPE));
+        let rotated_width = canvas.height;
+        let rotated_height = canvas.width;
+        let mut rotated = vec![0u8; rotated_width * rotated_height];
+
+        // Rotate -90 degrees: rotated[y][1599-x] = original[x][y]
+        for y in 0..canvas.height {
+            for x in 0..canvas.width {
+                let original_pixel = canvas.data[y * canvas.width + x].index();
+                let new_x = y;
+                let new_y = canvas.width - 1 - x;
+                rotated[new_y * rotated_width + new_x] = original_pixel;
+            }
+        }
+
+        let mut pixels_a = Vec::new();
+        let mut pixels_b = Vec::new();
+        for row in 0..rotated_height {
+            for col in 0..SPLIT_COL {
+                pixels_a.push(rotated[row * rotated_width + col]);
+            }
+            for col in SPLIT_COL..rotated_width {
+                pixels_b.push(rotated[row * rotated_width + col]);
+            }
+        }
+
+        let pack = |pixels: &[u8]| -> Vec<u8> {
+            pixels
+                .chunks(2)
+                .map(|pair| (pair[0] << 4) | (pair[1] & 0x0F))
+                .collect()
+        };
+        (pack(&pixels_a), pack(&pixels_b))
+    }
+
+    /// Where logical pixel `(x, y)` of `canvas` lands on the upright
+    /// landscape glass, written out per case rather than via [`Orientation`]
+    fn to_landscape(canvas: &Canvas, orientation: Orientation) -> Canvas {
+        let (w, h) = (canvas.width, canvas.height);
+        let mut landscape = Canvas::new();
+        for y in 0..h {
+            for x in 0..w {
+                let mx = if orientation.is_mirrored_horizontally() {
+                    w - 1 - x
+                } else {
+                    x
+                };
+                let my = if orientation.is_mirrored_vertically() {
+                    h - 1 - y
+                } else {
+                    y
+                };
+                let (px, py) = match orientation.rotation() {
+                    Rotation::Deg0 => (mx, my),
+                    Rotation::Deg90 => (my, HEIGHT - 1 - mx),
+                    Rotation::Deg180 => (WIDTH - 1 - mx, HEIGHT - 1 - my),
+                    Rotation::Deg270 => (WIDTH - 1 - my, mx),
+                };
+                landscape.data[py * WIDTH + px] = canvas.data[y * w + x];
+            }
+        }
+        landscape
+    }
+
+    fn random_canvas(orientation: Orientation, seed: &mut u32) -> Canvas {
+        let mut canvas = Canvas::with_orientation(orientation);
+        for pixel in canvas.as_mut_slice() {
+            *seed ^= *seed << 13;
+            *seed ^= *seed >> 17;
+            *seed ^= *seed << 5;
+            *pixel = Color::ALL[*seed as usize % Color::ALL.len()];
+        }
+        canvas
+    }
+
+    #[test]
+    fn rotate_and_split_into_matches_reference() {
+        let mut frame = PackedFrame::default();
+        let mut seed = 0x2545_f491_u32;
+
+        // Landscape against the original implementation
+        let canvas = random_canvas(Orientation::LANDSCAPE, &mut seed);
+        let (cs0, cs1) = reference(&canvas);
+        canvas.rotate_and_split_into(Orientation::LANDSCAPE, &mut frame);
+        assert!(frame.cs0 == cs0 && frame.cs1 == cs1, "landscape differs");
+        crate::PackedCanvas::from(&canvas)
+            .rotate_and_split_into(Orientation::LANDSCAPE, &mut frame);
+        assert!(
+            frame.cs0 == cs0 && frame.cs1 == cs1,
+            "packed landscape differs"
+        );
+
+        // Every other mounting against the same picture drawn in landscape
+        let mut expected = PackedFrame::default();
+        for orientation in [
+            Orientation::new(Rotation::Deg90),
+            Orientation::new(Rotation::Deg180),
+            Orientation::new(Rotation::Deg270).mirror_vertical(true),
+            Orientation::LANDSCAPE
+                .mirror_horizontal(true)
+                .mirror_vertical(true),
+            Orientation::new(Rotation::Deg90).mirror_horizontal(true),
+        ] {
+            let canvas = random_canvas(orientation, &mut seed);
+            to_landscape(&canvas, orientation)
+                .rotate_and_split_into(Orientation::LANDSCAPE, &mut expected);
+
+            canvas.rotate_and_split_into(orientation, &mut frame);
+            assert!(
+                frame.cs0 == expected.cs0 && frame.cs1 == expected.cs1,
+                "{orientation:?} differs"
+            );
+            crate::PackedCanvas::from(&canvas).rotate_and_split_into(orientation, &mut frame);
+            assert!(
+                frame.cs0 == expected.cs0 && frame.cs1 == expected.cs1,
+                "packed {orientation:?} differs"
+            );
+        }
+    }
+}
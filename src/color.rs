@@ -0,0 +1,158 @@
+//! The six Spectra 6 inks.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::error::{InkyError, ParseColorError};
+
+/// One of the six colors the panel can show
+///
+/// The discriminant is the color index sent to the controllers. Index 4 is
+/// not a color on this panel, so there is a gap between [`Color::Red`] and
+/// [`Color::Blue`].
+///
+/// # Example
+/// ```
+/// use inky_el133::Color;
+///
+/// assert_eq!(Color::try_from(3)?, Color::Red);
+/// assert_eq!("yellow".parse(), Ok(Color::Yellow));
+/// assert_eq!("#0000FF".parse(), Ok(Color::Blue));
+/// assert_eq!(Color::Green.to_string(), "green");
+/// assert!(Color::try_from(4).is_err());
+/// # Ok::<(), inky_el133::InkyError>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+#[repr(u8)]
+pub enum Color {
+    Black = 0,
+    #[default]
+    White = 1,
+    Yellow = 2,
+    Red = 3,
+    Blue = 5,
+    Green = 6,
+}
+
+impl Color {
+    /// Every color, in index order
+    pub const ALL: [Color; 6] = [
+        Color::Black,
+        Color::White,
+        Color::Yellow,
+        Color::Red,
+        Color::Blue,
+        Color::Green,
+    ];
+
+    /// Color index sent to the controllers
+    pub const fn index(self) -> u8 {
+        self as u8
+    }
+
+    /// Byte holding two pixels of this color, packed for the controllers
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    pub(crate) const fn pair(self) -> u8 {
+        self.index() * 0x11
+    }
+
+    /// Color of the low nibble of a packed byte
+    ///
+    /// Frames built by this crate only ever hold valid indices; anything else
+    /// reads as the default color.
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    pub(crate) fn from_nibble(nibble: u8) -> Self {
+        Color::try_from(nibble & 0x0F).unwrap_or_default()
+    }
+
+    /// Nominal sRGB value, as used by the reference Python driver
+    ///
+    /// The inks are noticeably darker and less saturated than this.
+    pub const fn rgb(self) -> [u8; 3] {
+        match self {
+            Color::Black => [0, 0, 0],
+            Color::White => [255, 255, 255],
+            Color::Yellow => [255, 255, 0],
+            Color::Red => [255, 0, 0],
+            Color::Blue => [0, 0, 255],
+            Color::Green => [0, 255, 0],
+        }
+    }
+
+    /// Lowercase English name
+    pub const fn name(self) -> &'static str {
+        match self {
+            Color::Black => "black",
+            Color::White => "white",
+            Color::Yellow => "yellow",
+            Color::Red => "red",
+            Color::Blue => "blue",
+            Color::Green => "green",
+        }
+    }
+}
+
+impl From<Color> for u8 {
+    fn from(color: Color) -> u8 {
+        color.index()
+    }
+}
+
+impl TryFrom<u8> for Color {
+    type Error = InkyError;
+
+    fn try_from(index: u8) -> Result<Self, Self::Error> {
+        match index {
+            0 => Ok(Color::Black),
+            1 => Ok(Color::White),
+            2 => Ok(Color::Yellow),
+            3 => Ok(Color::Red),
+            5 => Ok(Color::Blue),
+            6 => Ok(Color::Green),
+            _ => Err(InkyError::InvalidColor(index)),
+        }
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    /// Parse a color name (any case) or the hex form of its nominal RGB
+    /// value, with or without a leading `#`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(color) = Color::ALL
+            .into_iter()
+            .find(|color| color.name().eq_ignore_ascii_case(s))
+        {
+            return Ok(color);
+        }
+
+        let rgb = parse_hex(s.strip_prefix('#').unwrap_or(s)).ok_or(ParseColorError)?;
+        Color::ALL
+            .into_iter()
+            .find(|color| color.rgb() == rgb)
+            .ok_or(ParseColorError)
+    }
+}
+
+/// Parse `RRGGBB`
+fn parse_hex(hex: &str) -> Option<[u8; 3]> {
+    if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
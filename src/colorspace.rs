@@ -0,0 +1,288 @@
+//! sRGB to CIELAB and OKLab conversions, and the CIEDE2000 color difference.
+//!
+//! Every transcendental function comes from `libm` rather than `std`, so the
+//! results are bit-for-bit the same with or without `std` and on every
+//! platform.
+
+use libm::{atan2f, cbrtf, cosf, expf, hypotf, powf, sinf, sqrtf};
+
+/// sRGB channel value to linear light, for every possible byte
+pub(crate) fn linear_table() -> [f32; 256] {
+    let mut table = [0.0; 256];
+    for (value, linear) in table.iter_mut().enumerate() {
+        let c = value as f32 / 255.0;
+        *linear = if c <= 0.040_45 {
+            c / 12.92
+        } else {
+            powf((c + 0.055) / 1.055, 2.4)
+        };
+    }
+    table
+}
+
+/// Linear sRGB to CIELAB (D65 white point)
+pub(crate) fn lab([r, g, b]: [f32; 3]) -> [f32; 3] {
+    const WHITE: [f32; 3] = [0.950_47, 1.0, 1.088_83];
+
+    let xyz = [
+        0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b,
+        0.212_672_9 * r + 0.715_152_2 * g + 0.072_175 * b,
+        0.019_333_9 * r + 0.119_192 * g + 0.950_304_1 * b,
+    ];
+    let [fx, fy, fz] = [0, 1, 2].map(|i| lab_f(xyz[i] / WHITE[i]));
+
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+fn lab_f(t: f32) -> f32 {
+    const DELTA: f32 = 6.0 / 29.0;
+    if t > DELTA * DELTA * DELTA {
+        cbrtf(t)
+    } else {
+        t / (3.0 * DELTA * DELTA) + 4.0 / 29.0
+    }
+}
+
+/// Linear sRGB to OKLab
+pub(crate) fn oklab([r, g, b]: [f32; 3]) -> [f32; 3] {
+    let l = cbrtf(0.412_221_47 * r + 0.536_332_55 * g + 0.051_445_995 * b);
+    let m = cbrtf(0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b);
+    let s = cbrtf(0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b);
+
+    [
+        0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+        1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+        0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+    ]
+}
+
+/// Squared Euclidean distance
+pub(crate) fn distance_squared(a: [f32; 3], b: [f32; 3]) -> f32 {
+    (0..3).map(|i| (a[i] - b[i]) * (a[i] - b[i])).sum()
+}
+
+/// CIEDE2000 color difference between two CIELAB colors
+///
+/// Follows Sharma, Wu and Dalal, "The CIEDE2000 Color-Difference Formula:
+/// Implementation Notes" (2005), with unit weighting factors.
+pub(crate) fn ciede2000([l1, a1, b1]: [f32; 3], [l2, a2, b2]: [f32; 3]) -> f32 {
+    let c1 = hypotf(a1, b1);
+    let c2 = hypotf(a2, b2);
+    let c_mean = (c1 + c2) / 2.0;
+    let c_mean7 = pow7(c_mean);
+    let g = 0.5 * (1.0 - sqrtf(c_mean7 / (c_mean7 + pow7(25.0))));
+
+    let a1 = a1 * (1.0 + g);
+    let a2 = a2 * (1.0 + g);
+    let c1 = hypotf(a1, b1);
+    let c2 = hypotf(a2, b2);
+    let hue = |b: f32, a: f32| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            let h = atan2f(b, a).to_degrees();
+            if h < 0.0 { h + 360.0 } else { h }
+        }
+    };
+    let h1 = hue(b1, a1);
+    let h2 = hue(b2, a2);
+
+    let delta_l = l2 - l1;
+    let delta_c = c2 - c1;
+    let delta_h = if c1 * c2 == 0.0 {
+        0.0
+    } else if (h2 - h1).abs() <= 180.0 {
+        h2 - h1
+    } else if h2 - h1 > 180.0 {
+        h2 - h1 - 360.0
+    } else {
+        h2 - h1 + 360.0
+    };
+    let delta_h = 2.0 * sqrtf(c1 * c2) * sinf(delta_h.to_radians() / 2.0);
+
+    let l_mean = (l1 + l2) / 2.0;
+    let c_mean = (c1 + c2) / 2.0;
+    let h_mean = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+
+    let t = 1.0 - 0.17 * cosf((h_mean - 30.0).to_radians())
+        + 0.24 * cosf((2.0 * h_mean).to_radians())
+        + 0.32 * cosf((3.0 * h_mean + 6.0).to_radians())
+        - 0.20 * cosf((4.0 * h_mean - 63.0).to_radians());
+    let delta_theta = 30.0 * expf(-square((h_mean - 275.0) / 25.0));
+    let c_mean7 = pow7(c_mean);
+    let r_c = 2.0 * sqrtf(c_mean7 / (c_mean7 + pow7(25.0)));
+    let l50 = square(l_mean - 50.0);
+    let s_l = 1.0 + 0.015 * l50 / sqrtf(20.0 + l50);
+    let s_c = 1.0 + 0.045 * c_mean;
+    let s_h = 1.0 + 0.015 * c_mean * t;
+    let r_t = -sinf(2.0 * delta_theta.to_radians()) * r_c;
+
+    let dl = delta_l / s_l;
+    let dc = delta_c / s_c;
+    let dh = delta_h / s_h;
+    sqrtf(dl * dl + dc * dc + dh * dh + r_t * dc * dh)
+}
+
+fn square(x: f32) -> f32 {
+    x * x
+}
+
+fn pow7(x: f32) -> f32 {
+    let x3 = x * x * x;
+    x3 * x3 * x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: [f32; 3], expected: [f32; 3], tolerance: f32) {
+        for i in 0..3 {
+            assert!(
+                (actual[i] - expected[i]).abs() <= tolerance,
+                "{actual:?} != {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn linear_table_endpoints() {
+        let table = linear_table();
+        assert_eq!(table[0], 0.0);
+        assert!((table[128] - 0.215_861).abs() < 1e-5);
+        assert!((table[255] - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn lab_of_white_and_primaries() {
+        assert_close(lab([1.0, 1.0, 1.0]), [100.0, 0.0, 0.0], 0.01);
+        assert_close(lab([1.0, 0.0, 0.0]), [53.2408, 80.0925, 67.2032], 0.01);
+        assert_close(lab([0.0, 1.0, 0.0]), [87.7347, -86.1827, 83.1793], 0.01);
+        assert_close(lab([0.0, 0.0, 1.0]), [32.2970, 79.1875, -107.8602], 0.01);
+    }
+
+    /// Values from Björn Ottosson's OKLab reference implementation
+    #[test]
+    fn oklab_of_white_and_primaries() {
+        assert_close(oklab([1.0, 1.0, 1.0]), [1.0, 0.0, 0.0], 1e-3);
+        assert_close(
+            oklab([1.0, 0.0, 0.0]),
+            [0.627_955, 0.224_863, 0.125_846],
+            1e-3,
+        );
+        assert_close(
+            oklab([0.0, 1.0, 0.0]),
+            [0.866_440, -0.233_888, 0.179_498],
+            1e-3,
+        );
+        assert_close(
+            oklab([0.0, 0.0, 1.0]),
+            [0.452_014, -0.032_457, -0.311_528],
+            1e-3,
+        );
+    }
+
+    /// Test data from Table 1 of Sharma, Wu and Dalal (2005)
+    const SHARMA: [([f32; 3], [f32; 3], f32); 34] = [
+        ([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+        ([50.0, 3.1571, -77.2803], [50.0, 0.0, -82.7485], 2.8615),
+        ([50.0, 2.8361, -74.0200], [50.0, 0.0, -82.7485], 3.4412),
+        ([50.0, -1.3802, -84.2814], [50.0, 0.0, -82.7485], 1.0000),
+        ([50.0, -1.1848, -84.8006], [50.0, 0.0, -82.7485], 1.0000),
+        ([50.0, -0.9009, -85.5211], [50.0, 0.0, -82.7485], 1.0000),
+        ([50.0, 0.0, 0.0], [50.0, -1.0, 2.0], 2.3669),
+        ([50.0, -1.0, 2.0], [50.0, 0.0, 0.0], 2.3669),
+        ([50.0, 2.4900, -0.0010], [50.0, -2.4900, 0.0009], 7.1792),
+        ([50.0, 2.4900, -0.0010], [50.0, -2.4900, 0.0010], 7.1792),
+        ([50.0, 2.4900, -0.0010], [50.0, -2.4900, 0.0011], 7.2195),
+        ([50.0, 2.4900, -0.0010], [50.0, -2.4900, 0.0012], 7.2195),
+        ([50.0, -0.0010, 2.4900], [50.0, 0.0009, -2.4900], 4.8045),
+        ([50.0, -0.0010, 2.4900], [50.0, 0.0010, -2.4900], 4.8045),
+        ([50.0, -0.0010, 2.4900], [50.0, 0.0011, -2.4900], 4.7461),
+        ([50.0, 2.5, 0.0], [50.0, 0.0, -2.5], 4.3065),
+        ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.1492),
+        ([50.0, 2.5, 0.0], [61.0, -5.0, 29.0], 22.8977),
+        ([50.0, 2.5, 0.0], [56.0, -27.0, -3.0], 31.9030),
+        ([50.0, 2.5, 0.0], [58.0, 24.0, 15.0], 19.4535),
+        ([50.0, 2.5, 0.0], [50.0, 3.1736, 0.5854], 1.0000),
+        ([50.0, 2.5, 0.0], [50.0, 3.2972, 0.0], 1.0000),
+        ([50.0, 2.5, 0.0], [50.0, 1.8634, 0.5757], 1.0000),
+        ([50.0, 2.5, 0.0], [50.0, 3.2592, 0.3350], 1.0000),
+        (
+            [60.2574, -34.0099, 36.2677],
+            [60.4626, -34.1751, 39.4387],
+            1.2644,
+        ),
+        (
+            [63.0109, -31.0961, -5.8663],
+            [62.8187, -29.7946, -4.0864],
+            1.2630,
+        ),
+        (
+            [61.2901, 3.7196, -5.3901],
+            [61.4292, 2.2480, -4.9620],
+            1.8731,
+        ),
+        (
+            [35.0831, -44.1164, 3.7933],
+            [35.0232, -40.0716, 1.5901],
+            1.8645,
+        ),
+        (
+            [22.7233, 20.0904, -46.6940],
+            [23.0331, 14.9730, -42.5619],
+            2.0373,
+        ),
+        (
+            [36.4612, 47.8580, 18.3852],
+            [36.2715, 50.5065, 21.2231],
+            1.4146,
+        ),
+        (
+            [90.8027, -2.0831, 1.4410],
+            [91.1528, -1.6435, 0.0447],
+            1.4441,
+        ),
+        (
+            [90.9257, -0.5406, -0.9208],
+            [88.6381, -0.8985, -0.7239],
+            1.5381,
+        ),
+        (
+            [6.7747, -0.2908, -2.4247],
+            [5.8714, -0.0985, -2.2286],
+            0.6377,
+        ),
+        (
+            [2.0776, 0.0795, -1.1350],
+            [0.9033, -0.0636, -0.5514],
+            0.9082,
+        ),
+    ];
+
+    #[test]
+    fn ciede2000_matches_reference_pairs() {
+        for (i, (a, b, expected)) in SHARMA.into_iter().enumerate() {
+            let forward = ciede2000(a, b);
+            let backward = ciede2000(b, a);
+            assert!(
+                (forward - expected).abs() < 1e-3,
+                "pair {}: {forward}",
+                i + 1
+            );
+            assert!(
+                (backward - expected).abs() < 1e-3,
+                "pair {} reversed: {backward}",
+                i + 1
+            );
+        }
+    }
+}
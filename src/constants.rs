@@ -1,25 +1,40 @@
 //! Hardware configuration constants for the EL133UF1 display controller.
 
 // GPIO Pin Configuration
-#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
 pub const CS0_PIN: u8 = 26;
-#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
 pub const CS1_PIN: u8 = 16;
-#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
 pub const DC_PIN: u8 = 22;
-#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
 pub const RESET_PIN: u8 = 27;
-#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
 pub const BUSY_PIN: u8 = 17;
 
 // Display Dimensions
 pub const WIDTH: usize = 1600;
 pub const HEIGHT: usize = 1200;
 pub const SPLIT_COL: usize = 600;
+pub const ROW_BYTES: usize = SPLIT_COL / 2; // Packed bytes per native row of one controller
+#[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+pub const HALF_BYTES: usize = ROW_BYTES * WIDTH; // Packed bytes per controller
 
 // SPI Configuration
-#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
 pub const SPI_SPEED_HZ: u32 = 10_000_000;
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+pub const SPI_CHUNK_SIZE: usize = 4096; // inky_el133uf1.py:367-370
+
+// Reset Timing
+#[cfg_attr(not(any(feature = "std", feature = "embedded-hal")), allow(dead_code))]
+pub const RESET_PULSE_MS: u64 = 30; // inky_el133uf1.py:229-232
+
+// Linux Device Paths
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+pub const GPIOCHIP_PATH: &str = "/dev/gpiochip0";
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+pub const SPIDEV_PATH: &str = "/dev/spidev0.0";
 
 // EL133UF1 Commands
 pub const CMD_PSR: u8 = 0x00; // Panel Setting Register
@@ -28,6 +43,7 @@ pub const CMD_POF: u8 = 0x02; // Power Off
 pub const CMD_PON: u8 = 0x04; // Power On
 pub const CMD_BTST_N: u8 = 0x05; // Booster Soft Start VDDN
 pub const CMD_BTST_P: u8 = 0x06; // Booster Soft Start VDDP
+pub const CMD_DSLP: u8 = 0x07; // Deep Sleep
 pub const CMD_DTM: u8 = 0x10; // Data Transmission
 pub const CMD_DRF: u8 = 0x12; // Display Refresh
 pub const CMD_PLL: u8 = 0x30; // PLL Control
@@ -44,16 +60,5 @@ pub const CMD_CCSET: u8 = 0xE0; // Cascade Setting
 pub const CMD_PWS: u8 = 0xE3; // Power Saving
 pub const CMD_CMD66: u8 = 0xF0; // Command 0x66 (undocumented)
 
-// Color Constants
-pub const BLACK: u8 = 0;
-pub const WHITE: u8 = 1;
-pub const YELLOW: u8 = 2;
-pub const RED: u8 = 3;
-// Note: Color 4 is invalid/skipped
-pub const BLUE: u8 = 5;
-pub const GREEN: u8 = 6;
-
-// Validate color index
-pub fn is_valid_color(color: u8) -> bool {
-    matches!(color, BLACK | WHITE | YELLOW | RED | BLUE | GREEN)
-}
+/// Check code that must accompany `CMD_DSLP`
+pub const DSLP_CHECK: u8 = 0xA5;
@@ -1,69 +1,137 @@
+use core::time::Duration;
+
 use crate::error::Result;
+use crate::transport::{ChipSelect, Transport};
+
+#[cfg(feature = "std")]
+use {crate::constants::*, std::path::PathBuf};
 
-#[cfg(target_os = "linux")]
+#[cfg(all(feature = "std", target_os = "linux"))]
 use {
-    crate::constants::*,
-    gpio_cdev::{Chip, LineHandle, LineRequestFlags},
+    gpio_cdev::{Chip, EventRequestFlags, LineEventHandle, LineHandle, LineRequestFlags},
     spidev::{SpiModeFlags, Spidev, SpidevOptions},
+    std::io::{self, Write},
+    std::os::unix::io::AsRawFd,
     std::thread,
-    std::time::{Duration, Instant},
+    std::time::Instant,
 };
 
-/// Chip select options for dual-controller display
-#[derive(Debug, Clone, Copy)]
-pub(crate) enum ChipSelect {
-    CS0,
-    CS1,
-    Both,
+/// Wiring and bus settings used to open a [`DisplayController`]
+///
+/// The defaults match the Inky Impression HAT on a Raspberry Pi: GPIO lines on
+/// `/dev/gpiochip0` and the panel on `/dev/spidev0.0` at 10 MHz.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ControllerConfig {
+    /// GPIO character device holding the display lines (default
+    /// `/dev/gpiochip0`)
+    pub gpiochip: PathBuf,
+    /// SPI device the panel is attached to (default `/dev/spidev0.0`)
+    pub spidev: PathBuf,
+    /// SPI clock in Hz (default 10 MHz)
+    pub spi_speed_hz: u32,
+    /// Maximum bytes per SPI write (default 4096); must be non-zero
+    pub chunk_size: usize,
+    /// GPIO line on `gpiochip` for chip select 0 (default 26)
+    pub cs0_pin: u32,
+    /// GPIO line for chip select 1 (default 16)
+    pub cs1_pin: u32,
+    /// GPIO line for data/command select (default 22)
+    pub dc_pin: u32,
+    /// GPIO line for reset (default 27)
+    pub reset_pin: u32,
+    /// GPIO line for the busy signal (default 17)
+    pub busy_pin: u32,
+}
+
+#[cfg(feature = "std")]
+impl ControllerConfig {
+    /// Reject settings no bus can work with, before any device is opened
+    fn check(&self) -> Result<()> {
+        if self.chunk_size == 0 {
+            return Err(crate::error::InkyError::ZeroChunkSize);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for ControllerConfig {
+    fn default() -> Self {
+        Self {
+            gpiochip: PathBuf::from(GPIOCHIP_PATH),
+            spidev: PathBuf::from(SPIDEV_PATH),
+            spi_speed_hz: SPI_SPEED_HZ,
+            chunk_size: SPI_CHUNK_SIZE,
+            cs0_pin: CS0_PIN as u32,
+            cs1_pin: CS1_PIN as u32,
+            dc_pin: DC_PIN as u32,
+            reset_pin: RESET_PIN as u32,
+            busy_pin: BUSY_PIN as u32,
+        }
+    }
 }
 
 /// Low-level hardware controller for SPI and GPIO communication
-#[cfg(target_os = "linux")]
-pub(crate) struct DisplayController {
+#[cfg(all(feature = "std", target_os = "linux"))]
+pub struct DisplayController {
     spi: Spidev,
+    chunk_size: usize,
     cs0_pin: LineHandle,
     cs1_pin: LineHandle,
     dc_pin: LineHandle,
     reset_pin: LineHandle,
-    busy_pin: LineHandle,
+    busy_pin: LineEventHandle,
 }
 
-#[cfg(target_os = "linux")]
+#[cfg(all(feature = "std", target_os = "linux"))]
 impl DisplayController {
-    /// Initialize GPIO pins and SPI interface
+    /// Initialize GPIO pins and SPI interface with the default wiring
     pub fn new() -> Result<Self> {
-        let mut chip = Chip::new("/dev/gpiochip0")?;
+        Self::open(&ControllerConfig::default())
+    }
+
+    /// Initialize GPIO pins and SPI interface as described by `config`
+    ///
+    /// Fails with [`InkyError::ZeroChunkSize`](crate::InkyError::ZeroChunkSize)
+    /// if `config.chunk_size` is zero.
+    pub fn open(config: &ControllerConfig) -> Result<Self> {
+        config.check()?;
+        let mut chip = Chip::new(&config.gpiochip)?;
 
         let cs0_pin =
-            chip.get_line(CS0_PIN as u32)?
+            chip.get_line(config.cs0_pin)?
                 .request(LineRequestFlags::OUTPUT, 1, "inky-cs0")?;
 
         let cs1_pin =
-            chip.get_line(CS1_PIN as u32)?
+            chip.get_line(config.cs1_pin)?
                 .request(LineRequestFlags::OUTPUT, 1, "inky-cs1")?;
 
         let dc_pin =
-            chip.get_line(DC_PIN as u32)?
+            chip.get_line(config.dc_pin)?
                 .request(LineRequestFlags::OUTPUT, 0, "inky-dc")?;
 
         let reset_pin =
-            chip.get_line(RESET_PIN as u32)?
+            chip.get_line(config.reset_pin)?
                 .request(LineRequestFlags::OUTPUT, 1, "inky-reset")?;
 
-        let busy_pin =
-            chip.get_line(BUSY_PIN as u32)?
-                .request(LineRequestFlags::INPUT, 0, "inky-busy")?;
+        let busy_pin = chip.get_line(config.busy_pin)?.events(
+            LineRequestFlags::INPUT,
+            EventRequestFlags::FALLING_EDGE,
+            "inky-busy",
+        )?;
 
-        let mut spi = Spidev::open("/dev/spidev0.0")?;
+        let mut spi = Spidev::open(&config.spidev)?;
         let options = SpidevOptions::new()
             .bits_per_word(8)
-            .max_speed_hz(SPI_SPEED_HZ)
+            .max_speed_hz(config.spi_speed_hz)
             .mode(SpiModeFlags::SPI_MODE_0)
             .build();
         spi.configure(&options)?;
 
         Ok(Self {
             spi,
+            chunk_size: config.chunk_size,
             cs0_pin,
             cs1_pin,
             dc_pin,
@@ -72,19 +140,50 @@ impl DisplayController {
         })
     }
 
+    /// Block until a BUSY edge event is pending or `timeout` passes
+    fn poll_busy_edge(&self, timeout: Duration) -> Result<bool> {
+        let mut fd = libc::pollfd {
+            fd: self.busy_pin.as_raw_fd(),
+            events: libc::POLLIN | libc::POLLPRI,
+            revents: 0,
+        };
+        let timeout_ms = timeout.as_micros().div_ceil(1000).min(i32::MAX as u128) as i32;
+
+        // SAFETY: `fd` is a single valid pollfd and outlives the call.
+        let ready = unsafe { libc::poll(&mut fd, 1, timeout_ms) };
+        if ready < 0 {
+            let err = io::Error::last_os_error();
+            if err.kind() == io::ErrorKind::Interrupted {
+                return Ok(false);
+            }
+            return Err(err.into());
+        }
+
+        Ok(ready > 0)
+    }
+}
+
+#[cfg(all(feature = "std", target_os = "linux"))]
+impl Transport for DisplayController {
     /// Perform hardware reset of the display (inky_el133uf1.py:229-232)
-    pub fn reset(&mut self) -> Result<()> {
-        self.reset_pin.set_value(0)?;
-        thread::sleep(Duration::from_millis(30));
+    fn reset(&mut self) -> Result<()> {
+        self.set_reset(true)?;
+        thread::sleep(Duration::from_millis(RESET_PULSE_MS));
 
-        self.reset_pin.set_value(1)?;
-        thread::sleep(Duration::from_millis(30));
+        self.set_reset(false)?;
+        thread::sleep(Duration::from_millis(RESET_PULSE_MS));
 
         Ok(())
     }
 
-    /// Send command and optional data to specified chip select
-    pub fn send_command(&mut self, cs: ChipSelect, cmd: u8, data: &[u8]) -> Result<()> {
+    /// RESET is active low
+    fn set_reset(&mut self, asserted: bool) -> Result<()> {
+        self.reset_pin.set_value(u8::from(!asserted))?;
+        Ok(())
+    }
+
+    /// Select the controller(s) and send a command byte
+    fn begin_command(&mut self, cs: ChipSelect, cmd: u8) -> Result<()> {
         match cs {
             ChipSelect::CS0 => self.cs0_pin.set_value(0)?,
             ChipSelect::CS1 => self.cs1_pin.set_value(0)?,
@@ -95,21 +194,23 @@ impl DisplayController {
         }
 
         self.dc_pin.set_value(0)?;
-        thread::sleep(Duration::from_millis(300)); // inky_el133uf1.py:384
-
-        use std::io::Write;
         self.spi.write_all(&[cmd])?;
+        Ok(())
+    }
 
-        // Chunk into 4KB blocks (inky_el133uf1.py:367-370)
-        if !data.is_empty() {
-            self.dc_pin.set_value(1)?;
+    /// Send data for the current command in `chunk_size` blocks (inky_el133uf1.py:367-370)
+    fn write_data(&mut self, data: &[u8]) -> Result<()> {
+        self.dc_pin.set_value(1)?;
 
-            const CHUNK_SIZE: usize = 4096;
-            for chunk in data.chunks(CHUNK_SIZE) {
-                self.spi.write_all(chunk)?;
-            }
+        for chunk in data.chunks(self.chunk_size) {
+            self.spi.write_all(chunk)?;
         }
 
+        Ok(())
+    }
+
+    /// Deselect both controllers
+    fn end_command(&mut self) -> Result<()> {
         self.cs0_pin.set_value(1)?;
         self.cs1_pin.set_value(1)?;
         self.dc_pin.set_value(0)?;
@@ -118,47 +219,79 @@ impl DisplayController {
     }
 
     /// Wait for busy pin to go low (display ready) - inky_el133uf1.py:261-270
-    pub fn wait_busy(&mut self, timeout_ms: u64) -> Result<()> {
+    ///
+    /// Sleeps on falling-edge events from the BUSY line instead of polling,
+    /// so it returns as soon as the controller finishes.
+    fn wait_busy(&mut self, timeout_ms: u64) -> Result<Option<Duration>> {
         let timeout = Duration::from_millis(timeout_ms);
         let start = Instant::now();
 
-        // If busy_pin is HIGH initially, display isn't connected - just sleep
-        if self.busy_pin.get_value()? == 1 {
-            thread::sleep(timeout);
-            return Ok(());
-        }
-
         while self.busy_pin.get_value()? == 1 {
-            if start.elapsed() > timeout {
-                eprintln!("Warning: Busy wait timed out after {} ms", timeout_ms);
-                return Ok(());
+            let remaining = timeout.saturating_sub(start.elapsed());
+            if remaining.is_zero() {
+                return Ok(None);
+            }
+
+            // Consume the edge; the loop re-reads the level in case it was stale
+            if self.poll_busy_edge(remaining)? {
+                self.busy_pin.get_event()?;
             }
-            thread::sleep(Duration::from_millis(100));
         }
 
-        Ok(())
+        Ok(Some(start.elapsed()))
+    }
+
+    fn is_busy(&mut self) -> Result<bool> {
+        Ok(self.busy_pin.get_value()? == 1)
+    }
+
+    fn delay_ms(&mut self, ms: u64) {
+        thread::sleep(Duration::from_millis(ms));
     }
 }
 
 // Stub implementation for non-Linux platforms
-#[cfg(not(target_os = "linux"))]
-pub(crate) struct DisplayController;
+#[cfg(not(all(feature = "std", target_os = "linux")))]
+pub struct DisplayController;
 
-#[cfg(not(target_os = "linux"))]
+#[cfg(not(all(feature = "std", target_os = "linux")))]
 impl DisplayController {
     pub fn new() -> Result<Self> {
         Err(crate::error::InkyError::UnsupportedPlatform)
     }
 
-    pub fn reset(&mut self) -> Result<()> {
+    #[cfg(feature = "std")]
+    pub fn open(config: &ControllerConfig) -> Result<Self> {
+        config.check()?;
+        Err(crate::error::InkyError::UnsupportedPlatform)
+    }
+}
+
+#[cfg(not(all(feature = "std", target_os = "linux")))]
+impl Transport for DisplayController {
+    fn reset(&mut self) -> Result<()> {
         Err(crate::error::InkyError::UnsupportedPlatform)
     }
 
-    pub fn send_command(&mut self, _cs: ChipSelect, _cmd: u8, _data: &[u8]) -> Result<()> {
+    fn begin_command(&mut self, _cs: ChipSelect, _cmd: u8) -> Result<()> {
         Err(crate::error::InkyError::UnsupportedPlatform)
     }
 
-    pub fn wait_busy(&mut self, _timeout_ms: u64) -> Result<()> {
+    fn write_data(&mut self, _data: &[u8]) -> Result<()> {
         Err(crate::error::InkyError::UnsupportedPlatform)
     }
+
+    fn end_command(&mut self) -> Result<()> {
+        Err(crate::error::InkyError::UnsupportedPlatform)
+    }
+
+    fn wait_busy(&mut self, _timeout_ms: u64) -> Result<Option<Duration>> {
+        Err(crate::error::InkyError::UnsupportedPlatform)
+    }
+
+    fn is_busy(&mut self) -> Result<bool> {
+        Err(crate::error::InkyError::UnsupportedPlatform)
+    }
+
+    fn delay_ms(&mut self, _ms: u64) {}
 }
@@ -0,0 +1,557 @@
+//! Dithering RGB frames down to the six panel colors.
+//!
+//! [`ErrorDiffusion`] gives the smoothest gradients. [`Ordered`] thresholds
+//! each pixel against a fixed, tiled matrix instead, so an unchanged region
+//! dithers the same way in every frame and no error "worms" appear.
+//!
+//! A [`Dither`] reads a packed RGB8 frame (three bytes per pixel, row-major,
+//! the layout of `image::RgbImage`) and writes the result straight into a
+//! [`Canvas`], matching colors through a [`Palette`]. Error propagation and
+//! thresholds use integer arithmetic and the perceptual metrics use software
+//! floating point, so the output for a given input is identical on every
+//! platform, with or without `std`.
+//!
+//! # Example
+//! ```
+//! use inky_el133::dither::{Dither, ErrorDiffusion, Kernel};
+//! use inky_el133::{Canvas, Color, Palette};
+//!
+//! // Against pure black and white, a flat mid grey comes out as a mix of both
+//! let grey = vec![128u8; 1600 * 1200 * 3];
+//!
+//! let mut canvas = Canvas::new();
+//! ErrorDiffusion::new(Kernel::FloydSteinberg).dither(&grey, &Palette::NOMINAL, &mut canvas)?;
+//!
+//! let black = canvas.as_slice().iter().filter(|&&c| c == Color::Black).count();
+//! assert!(black > 1600 * 1200 / 3 && black < 1600 * 1200 * 2 / 3);
+//! # Ok::<(), inky_el133::InkyError>(())
+//! ```
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::error::{InkyError, Result};
+use crate::{Canvas, Palette};
+
+/// Turns an RGB frame into panel colors
+pub trait Dither {
+    /// Dither `rgb` into `canvas`
+    ///
+    /// `rgb` holds three bytes per pixel in row-major order and must cover the
+    /// whole canvas, otherwise [`InkyError::InvalidBufferSize`] is returned.
+    fn dither(&self, rgb: &[u8], palette: &Palette, canvas: &mut Canvas) -> Result<()>;
+}
+
+/// Error-diffusion kernel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kernel {
+    /// Floyd–Steinberg: 4 neighbours over 2 rows
+    FloydSteinberg,
+    /// Atkinson: spreads only 3/4 of the error, giving lighter, higher contrast results
+    Atkinson,
+    /// Jarvis, Judice and Ninke: 12 neighbours over 3 rows
+    JarvisJudiceNinke,
+    /// Stucki: like Jarvis–Judice–Ninke with sharper weights
+    Stucki,
+    /// Sierra (three-row): close to Jarvis–Judice–Ninke, slightly cheaper
+    Sierra,
+}
+
+impl Kernel {
+    /// `(dx, dy, weight)` taps and the divisor the weights are relative to
+    fn taps(self) -> (&'static [(isize, usize, i32)], i32) {
+        match self {
+            Kernel::FloydSteinberg => (&[(1, 0, 7), (-1, 1, 3), (0, 1, 5), (1, 1, 1)], 16),
+            Kernel::Atkinson => (
+                &[
+                    (1, 0, 1),
+                    (2, 0, 1),
+                    (-1, 1, 1),
+                    (0, 1, 1),
+                    (1, 1, 1),
+                    (0, 2, 1),
+                ],
+                8,
+            ),
+            Kernel::JarvisJudiceNinke => (
+                &[
+                    (1, 0, 7),
+                    (2, 0, 5),
+                    (-2, 1, 3),
+                    (-1, 1, 5),
+                    (0, 1, 7),
+                    (1, 1, 5),
+                    (2, 1, 3),
+                    (-2, 2, 1),
+                    (-1, 2, 3),
+                    (0, 2, 5),
+                    (1, 2, 3),
+                    (2, 2, 1),
+                ],
+                48,
+            ),
+            Kernel::Stucki => (
+                &[
+                    (1, 0, 8),
+                    (2, 0, 4),
+                    (-2, 1, 2),
+                    (-1, 1, 4),
+                    (0, 1, 8),
+                    (1, 1, 4),
+                    (2, 1, 2),
+                    (-2, 2, 1),
+                    (-1, 2, 2),
+                    (0, 2, 4),
+                    (1, 2, 2),
+                    (2, 2, 1),
+                ],
+                42,
+            ),
+            Kernel::Sierra => (
+                &[
+                    (1, 0, 5),
+                    (2, 0, 3),
+                    (-2, 1, 2),
+                    (-1, 1, 4),
+                    (0, 1, 5),
+                    (1, 1, 4),
+                    (2, 1, 2),
+                    (-1, 2, 2),
+                    (0, 2, 3),
+                    (1, 2, 2),
+                ],
+                32,
+            ),
+        }
+    }
+}
+
+/// Rows of pending error a kernel can reach, including the current one
+const ROWS: usize = 3;
+/// Columns of padding on each side so taps never index out of range
+const PAD: usize = 2;
+
+/// Error-diffusion dithering with a choice of [`Kernel`]
+///
+/// Scans serpentine (alternating direction every row) by default, which
+/// avoids the diagonal drift of plain left-to-right scanning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorDiffusion {
+    kernel: Kernel,
+    serpentine: bool,
+    error_limit: u8,
+}
+
+impl ErrorDiffusion {
+    /// Serpentine error diffusion with `kernel` and no error clamping
+    pub const fn new(kernel: Kernel) -> Self {
+        Self {
+            kernel,
+            serpentine: true,
+            error_limit: u8::MAX,
+        }
+    }
+
+    /// Alternate scan direction every row (default `true`)
+    pub const fn serpentine(mut self, serpentine: bool) -> Self {
+        self.serpentine = serpentine;
+        self
+    }
+
+    /// Clamp each channel's quantization error to `±limit` before spreading it
+    /// (default 255, no clamping)
+    ///
+    /// With only six inks some colors are far from every palette entry, and
+    /// the full error smears them across neighbouring areas. A limit around
+    /// 64–128 keeps that bleeding local at the cost of some accuracy.
+    pub const fn error_limit(mut self, limit: u8) -> Self {
+        self.error_limit = limit;
+        self
+    }
+}
+
+impl Dither for ErrorDiffusion {
+    fn dither(&self, rgb: &[u8], palette: &Palette, canvas: &mut Canvas) -> Result<()> {
+        let width = canvas.width();
+        if rgb.len() != width * canvas.height() * 3 {
+            return Err(InkyError::InvalidBufferSize);
+        }
+
+        let (taps, divisor) = self.kernel.taps();
+        let limit = self.error_limit as i32;
+        let stride = width + 2 * PAD;
+        let matcher = palette.matcher();
+
+        // Ring of ROWS rows of accumulated `error * weight`, one per channel
+        let mut pending: Vec<[i32; 3]> = vec![[0; 3]; ROWS * stride];
+
+        for (y, (src, dst)) in rgb
+            .chunks_exact(width * 3)
+            .zip(canvas.as_mut_slice().chunks_exact_mut(width))
+            .enumerate()
+        {
+            let reverse = self.serpentine && y % 2 == 1;
+            let current = (y % ROWS) * stride;
+
+            for i in 0..width {
+                let x = if reverse { width - 1 - i } else { i };
+
+                let acc = pending[current + x + PAD];
+                let mut wanted = [0u8; 3];
+                for c in 0..3 {
+                    let value = src[x * 3 + c] as i32 + div_round(acc[c], divisor);
+                    wanted[c] = value.clamp(0, 255) as u8;
+                }
+
+                let color = matcher.nearest(wanted);
+                dst[x] = color;
+
+                let got = palette.rgb(color);
+                let mut error = [0i32; 3];
+                for c in 0..3 {
+                    error[c] = (wanted[c] as i32 - got[c] as i32).clamp(-limit, limit);
+                }
+
+                for &(dx, dy, weight) in taps {
+                    let dx = if reverse { -dx } else { dx };
+                    let tx = (x + PAD).wrapping_add_signed(dx);
+                    let row = ((y + dy) % ROWS) * stride;
+                    let slot = &mut pending[row + tx];
+                    for c in 0..3 {
+                        slot[c] += error[c] * weight;
+                    }
+                }
+            }
+
+            // This row is done; it becomes the furthest row ahead
+            pending[current..current + stride].fill([0; 3]);
+        }
+
+        Ok(())
+    }
+}
+
+/// `n / d` rounded to nearest, for positive `d`
+fn div_round(n: i32, d: i32) -> i32 {
+    if n >= 0 {
+        (n + d / 2) / d
+    } else {
+        (n - d / 2) / d
+    }
+}
+
+/// Threshold matrix for [`Ordered`] dithering
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Matrix {
+    /// 2×2 Bayer matrix, 4 levels
+    Bayer2,
+    /// 4×4 Bayer matrix, 16 levels
+    Bayer4,
+    /// 8×8 Bayer matrix, 64 levels
+    Bayer8,
+    /// 16×16 Bayer matrix, 256 levels
+    Bayer16,
+    /// Bundled 64×64 blue-noise texture, 256 levels
+    ///
+    /// Generated once with the void-and-cluster method (Gaussian σ = 1.5) and
+    /// shipped with the crate, so results never change between builds.
+    BlueNoise,
+}
+
+/// 64×64 blue-noise ranks scaled to 0..256, row-major
+static BLUE_NOISE: &[u8; BLUE_NOISE_SIZE * BLUE_NOISE_SIZE] = include_bytes!("blue_noise_64.bin");
+const BLUE_NOISE_SIZE: usize = 64;
+
+impl Matrix {
+    /// Threshold at `(x, y)` in 0..256 and the step between adjacent levels
+    fn threshold(self, x: usize, y: usize) -> (i32, i32) {
+        let bits = match self {
+            Matrix::Bayer2 => 1,
+            Matrix::Bayer4 => 2,
+            Matrix::Bayer8 => 3,
+            Matrix::Bayer16 => 4,
+            Matrix::BlueNoise => {
+                let index = (y % BLUE_NOISE_SIZE) * BLUE_NOISE_SIZE + x % BLUE_NOISE_SIZE;
+                return (BLUE_NOISE[index] as i32, 1);
+            }
+        };
+        let shift = 8 - 2 * bits;
+        ((bayer(x, y, bits) << shift) as i32, 1 << shift)
+    }
+}
+
+/// Entry of the `2^bits`-square Bayer matrix at `(x, y)`, tiled
+///
+/// Built from the recursive definition `M(2n) = [[4M, 4M+2], [4M+3, 4M+1]]`:
+/// each coordinate bit selects a quadrant, lower bits being more significant.
+fn bayer(x: usize, y: usize, bits: u32) -> u32 {
+    let mut value = 0;
+    for i in 0..bits {
+        let xb = (x >> i) as u32 & 1;
+        let yb = (y >> i) as u32 & 1;
+        value |= (((xb ^ yb) << 1) | yb) << (2 * (bits - 1 - i));
+    }
+    value
+}
+
+/// Ordered dithering against a tiled threshold [`Matrix`]
+///
+/// Each pixel is offset by its threshold, scaled to `±strength / 2` on every
+/// channel, and then matched to the nearest palette color. The result for a
+/// pixel depends only on its value and position.
+///
+/// # Example
+/// ```
+/// use inky_el133::dither::{Dither, Matrix, Ordered};
+/// use inky_el133::{Canvas, Palette};
+///
+/// let frame = vec![100u8; 1600 * 1200 * 3];
+/// let ordered = Ordered::new(Matrix::BlueNoise);
+///
+/// let mut first = Canvas::new();
+/// let mut second = Canvas::new();
+/// ordered.dither(&frame, &Palette::default(), &mut first)?;
+/// ordered.dither(&frame, &Palette::default(), &mut second)?;
+/// assert_eq!(first, second);
+/// # Ok::<(), inky_el133::InkyError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ordered {
+    matrix: Matrix,
+    strength: u8,
+}
+
+impl Ordered {
+    /// Ordered dithering with `matrix` at full strength
+    pub const fn new(matrix: Matrix) -> Self {
+        Self {
+            matrix,
+            strength: u8::MAX,
+        }
+    }
+
+    /// Peak-to-peak size of the threshold offset per channel (default 255)
+    ///
+    /// Full strength lets a flat area blend any two neighbouring inks;
+    /// lower values leave colors close to an ink solid and reduce noise.
+    pub const fn strength(mut self, strength: u8) -> Self {
+        self.strength = strength;
+        self
+    }
+}
+
+impl Dither for Ordered {
+    fn dither(&self, rgb: &[u8], palette: &Palette, canvas: &mut Canvas) -> Result<()> {
+        let width = canvas.width();
+        if rgb.len() != width * canvas.height() * 3 {
+            return Err(InkyError::InvalidBufferSize);
+        }
+
+        let strength = self.strength as i32;
+        let matcher = palette.matcher();
+        for (y, (src, dst)) in rgb
+            .chunks_exact(width * 3)
+            .zip(canvas.as_mut_slice().chunks_exact_mut(width))
+            .enumerate()
+        {
+            for (x, (pixel, out)) in src.chunks_exact(3).zip(dst.iter_mut()).enumerate() {
+                // Centre of the threshold's level, mapped to -strength/2..strength/2
+                let (threshold, step) = self.matrix.threshold(x, y);
+                let offset = (2 * threshold + step - 256) * strength / 512;
+
+                let wanted = [0, 1, 2].map(|c| (pixel[c] as i32 + offset).clamp(0, 255) as u8);
+                *out = matcher.nearest(wanted);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    const KERNELS: [Kernel; 5] = [
+        Kernel::FloydSteinberg,
+        Kernel::Atkinson,
+        Kernel::JarvisJudiceNinke,
+        Kernel::Stucki,
+        Kernel::Sierra,
+    ];
+
+    /// White canvas of any size; the public constructors only make full frames
+    fn canvas(width: usize, height: usize) -> Canvas {
+        Canvas::from_raw(vec![Color::White; width * height], width, height)
+    }
+
+    fn flat(rgb: [u8; 3], width: usize, height: usize) -> Vec<u8> {
+        rgb.repeat(width * height)
+    }
+
+    /// Hue sweep across, brightness ramp down, so every ink gets matched
+    fn sweep(width: usize, height: usize) -> Vec<u8> {
+        let mut rgb = Vec::with_capacity(width * height * 3);
+        for y in 0..height {
+            for x in 0..width {
+                rgb.extend([
+                    (x * 255 / (width - 1)) as u8,
+                    (y * 255 / (height - 1)) as u8,
+                    ((x + y) * 4 % 256) as u8,
+                ]);
+            }
+        }
+        rgb
+    }
+
+    /// FNV-1a over the color indices
+    fn fingerprint(canvas: &Canvas) -> u64 {
+        canvas
+            .as_slice()
+            .iter()
+            .fold(0xcbf2_9ce4_8422_2325, |hash, color| {
+                (hash ^ color.index() as u64).wrapping_mul(0x0000_0100_0000_01b3)
+            })
+    }
+
+    #[test]
+    fn ink_colors_stay_solid_under_every_kernel() {
+        let palette = Palette::default();
+        for kernel in KERNELS {
+            for color in Color::ALL {
+                let mut out = canvas(16, 8);
+                ErrorDiffusion::new(kernel)
+                    .dither(&flat(palette.rgb(color), 16, 8), &palette, &mut out)
+                    .unwrap();
+                assert!(
+                    out.as_slice().iter().all(|&c| c == color),
+                    "{kernel:?} {color:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn kernel_weights_sum_to_divisor() {
+        for kernel in KERNELS {
+            let (taps, divisor) = kernel.taps();
+            let sum: i32 = taps.iter().map(|&(_, _, weight)| weight).sum();
+            if kernel == Kernel::Atkinson {
+                // Deliberately spreads only 3/4 of the error
+                assert_eq!(sum * 4, divisor * 3);
+            } else {
+                assert_eq!(sum, divisor, "{kernel:?}");
+            }
+            for &(dx, dy, _) in taps {
+                assert!(dx.unsigned_abs() <= PAD && dy < ROWS, "{kernel:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn serpentine_changes_scan_order() {
+        let rgb = sweep(32, 16);
+        let mut serpentine = canvas(32, 16);
+        let mut raster = canvas(32, 16);
+
+        let kernel = ErrorDiffusion::new(Kernel::FloydSteinberg);
+        kernel
+            .dither(&rgb, &Palette::NOMINAL, &mut serpentine)
+            .unwrap();
+        kernel
+            .serpentine(false)
+            .dither(&rgb, &Palette::NOMINAL, &mut raster)
+            .unwrap();
+
+        // The first row is scanned left to right either way
+        assert_eq!(serpentine.as_slice()[..32], raster.as_slice()[..32]);
+        assert_ne!(serpentine, raster);
+    }
+
+    #[test]
+    fn zero_error_limit_spreads_nothing() {
+        let grey = [128; 3];
+        let mut out = canvas(16, 8);
+        ErrorDiffusion::new(Kernel::FloydSteinberg)
+            .error_limit(0)
+            .dither(&flat(grey, 16, 8), &Palette::NOMINAL, &mut out)
+            .unwrap();
+
+        let nearest = Palette::NOMINAL.nearest(grey);
+        assert!(out.as_slice().iter().all(|&c| c == nearest));
+
+        // Unclamped, the same grey mixes black and white
+        ErrorDiffusion::new(Kernel::FloydSteinberg)
+            .dither(&flat(grey, 16, 8), &Palette::NOMINAL, &mut out)
+            .unwrap();
+        assert!(out.as_slice().iter().any(|&c| c != nearest));
+    }
+
+    #[test]
+    fn bayer_matrices_match_known_values() {
+        let matrix = |bits: u32| {
+            let size = 1 << bits;
+            (0..size)
+                .map(|y| (0..size).map(|x| bayer(x, y, bits)).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(matrix(1), [[0, 2], [3, 1]]);
+        assert_eq!(
+            matrix(2),
+            [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]]
+        );
+
+        // Larger matrices hold every level once
+        for bits in [3, 4] {
+            let mut levels: Vec<u32> = matrix(bits).concat();
+            levels.sort_unstable();
+            assert!(levels.iter().copied().eq(0..1 << (2 * bits)));
+        }
+
+        // Thresholds are levels scaled to 0..256, tiled
+        assert_eq!(Matrix::Bayer2.threshold(1, 0), (128, 64));
+        assert_eq!(
+            Matrix::Bayer16.threshold(16 + 3, 32 + 5),
+            Matrix::Bayer16.threshold(3, 5)
+        );
+    }
+
+    #[test]
+    fn blue_noise_is_a_64_square_of_ranks() {
+        assert_eq!(BLUE_NOISE.len(), BLUE_NOISE_SIZE * BLUE_NOISE_SIZE);
+
+        // 4096 ranks scaled to 256 levels, 16 pixels each
+        let mut counts = [0; 256];
+        for &level in BLUE_NOISE {
+            counts[level as usize] += 1;
+        }
+        assert!(counts.iter().all(|&n| n == 16));
+
+        assert_eq!(
+            Matrix::BlueNoise.threshold(64 + 7, 128 + 9),
+            Matrix::BlueNoise.threshold(7, 9)
+        );
+    }
+
+    /// The default palette matches in OKLab with floating point. These
+    /// fingerprints must not change between builds, with or without `std`,
+    /// or from one platform to the next.
+    #[test]
+    fn default_palette_output_is_fixed() {
+        let rgb = sweep(64, 48);
+        let palette = Palette::default();
+        let mut out = canvas(64, 48);
+
+        ErrorDiffusion::new(Kernel::FloydSteinberg)
+            .dither(&rgb, &palette, &mut out)
+            .unwrap();
+        assert!(Color::ALL.iter().all(|c| out.as_slice().contains(c)));
+        assert_eq!(fingerprint(&out), 16_707_130_063_527_814_109);
+
+        Ordered::new(Matrix::BlueNoise)
+            .dither(&rgb, &palette, &mut out)
+            .unwrap();
+        assert_eq!(fingerprint(&out), 1_179_934_622_184_124_249);
+    }
+}
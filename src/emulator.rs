@@ -0,0 +1,468 @@
+//! Software model of the EL133UF1 panel.
+//!
+//! [`PanelEmulator`] is a [`Transport`] that decodes the command stream sent by
+//! [`InkyDisplay`](crate::InkyDisplay) the way the two cascaded controllers
+//! would: `CMD_DTM` loads each controller's frame memory, `CMD_PON`/`CMD_POF`
+//! switch the high voltage supply, `CMD_DRF` copies frame memory onto the
+//! glass and `CMD_DSLP` ignores everything until the next reset. The glass
+//! contents can then be read back in landscape (1600×1200) or oriented
+//! coordinates, or written out as a PNG.
+//!
+//! Anything a real panel would reject or silently mis-render is recorded as a
+//! [`Violation`] instead of failing the call, so a whole session can be
+//! inspected afterwards.
+
+use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::constants::*;
+use crate::error::Result;
+use crate::transport::{ChipSelect, Transport};
+use crate::{Color, Orientation};
+
+/// Number of packed bytes one controller expects per `CMD_DTM`
+pub use crate::constants::HALF_BYTES as FRAME_BYTES;
+
+/// PNG color for glass holding an index that is not a valid color, chosen to
+/// stand out
+const INVALID_RGB: [u8; 3] = [255, 0, 255];
+
+/// Protocol errors detected by [`PanelEmulator`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// `CMD_DTM` carried the wrong number of bytes for one controller
+    DataLength {
+        cs: ChipSelect,
+        expected: usize,
+        actual: usize,
+    },
+    /// `CMD_DRF` was sent while the controller was powered off
+    RefreshBeforePowerOn { cs: ChipSelect },
+    /// `CMD_DRF` was sent before the controller received its init sequence
+    RefreshWithoutInit { cs: ChipSelect },
+    /// A command reached the controller while it was in deep sleep
+    CommandWhileAsleep { cs: ChipSelect, cmd: u8 },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::DataLength {
+                cs,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{:?}: DTM carried {} bytes, expected {}",
+                cs, actual, expected
+            ),
+            Violation::RefreshBeforePowerOn { cs } => write!(f, "{:?}: DRF before PON", cs),
+            Violation::RefreshWithoutInit { cs } => write!(f, "{:?}: DRF without init", cs),
+            Violation::CommandWhileAsleep { cs, cmd } => {
+                write!(f, "{:?}: command 0x{:02X} during deep sleep", cs, cmd)
+            }
+        }
+    }
+}
+
+/// State of one of the two cascaded controllers
+#[derive(Clone)]
+struct Controller {
+    cs: ChipSelect,
+    got_psr: bool,
+    got_tres: bool,
+    powered: bool,
+    /// Set by `CMD_DSLP`; cleared only by reset
+    asleep: bool,
+    /// Packed frame memory, laid out exactly as sent with `CMD_DTM`
+    ram: Vec<u8>,
+    /// Packed contents currently visible on this half of the glass
+    glass: Vec<u8>,
+}
+
+impl Controller {
+    fn new(cs: ChipSelect) -> Self {
+        let white = Color::White.pair();
+        Self {
+            cs,
+            got_psr: false,
+            got_tres: false,
+            powered: false,
+            asleep: false,
+            ram: vec![white; FRAME_BYTES],
+            glass: vec![white; FRAME_BYTES],
+        }
+    }
+
+    fn initialized(&self) -> bool {
+        self.got_psr && self.got_tres
+    }
+
+    fn command(&mut self, cmd: u8, data: &[u8], violations: &mut Vec<Violation>) -> bool {
+        if self.asleep {
+            violations.push(Violation::CommandWhileAsleep { cs: self.cs, cmd });
+            return false;
+        }
+
+        match cmd {
+            CMD_PSR => self.got_psr = true,
+            CMD_TRES => self.got_tres = true,
+            CMD_PON => self.powered = true,
+            CMD_POF => self.powered = false,
+            CMD_DSLP if data.first() == Some(&DSLP_CHECK) => self.asleep = true,
+            CMD_DTM => {
+                if data.len() != FRAME_BYTES {
+                    violations.push(Violation::DataLength {
+                        cs: self.cs,
+                        expected: FRAME_BYTES,
+                        actual: data.len(),
+                    });
+                }
+                let len = data.len().min(FRAME_BYTES);
+                self.ram[..len].copy_from_slice(&data[..len]);
+            }
+            CMD_DRF => {
+                if !self.initialized() {
+                    violations.push(Violation::RefreshWithoutInit { cs: self.cs });
+                    return false;
+                }
+                if !self.powered {
+                    violations.push(Violation::RefreshBeforePowerOn { cs: self.cs });
+                    return false;
+                }
+                self.glass.copy_from_slice(&self.ram);
+                return true;
+            }
+            _ => {}
+        }
+        false
+    }
+
+    /// Color index at native panel coordinates local to this controller
+    fn glass_pixel(&self, row: usize, col: usize) -> u8 {
+        let index = row * SPLIT_COL + col;
+        let byte = self.glass[index / 2];
+        if index.is_multiple_of(2) {
+            byte >> 4
+        } else {
+            byte & 0x0F
+        }
+    }
+}
+
+/// Emulated EL133UF1 panel usable as a [`Transport`].
+///
+/// # Example
+/// ```
+/// use inky_el133::{InkyDisplay, colors};
+/// use inky_el133::emulator::PanelEmulator;
+///
+/// let mut display = InkyDisplay::with_transport(PanelEmulator::new())?;
+/// display.fill(colors::WHITE);
+/// display.set_pixel(10, 20, colors::RED)?;
+/// display.show()?;
+///
+/// let panel = display.transport();
+/// assert!(panel.violations().is_empty());
+/// assert_eq!(panel.pixel(10, 20), colors::RED.index());
+/// assert_eq!(panel.pixel(11, 20), colors::WHITE.index());
+///
+/// let mut png = Vec::new();
+/// panel.write_png(&mut png).unwrap();
+/// # Ok::<(), inky_el133::InkyError>(())
+/// ```
+#[derive(Clone)]
+pub struct PanelEmulator {
+    controllers: [Controller; 2],
+    /// Command currently being received, applied on `end_command`
+    pending: Option<(ChipSelect, u8, Vec<u8>)>,
+    violations: Vec<Violation>,
+    refreshes: usize,
+}
+
+impl Default for PanelEmulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for PanelEmulator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PanelEmulator")
+            .field("violations", &self.violations)
+            .field("refreshes", &self.refreshes)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PanelEmulator {
+    /// Create an emulated panel showing all white, before reset or init
+    pub fn new() -> Self {
+        Self {
+            controllers: [
+                Controller::new(ChipSelect::CS0),
+                Controller::new(ChipSelect::CS1),
+            ],
+            pending: None,
+            violations: Vec::new(),
+            refreshes: 0,
+        }
+    }
+
+    /// Protocol violations observed so far, oldest first
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations
+    }
+
+    /// Number of completed `CMD_DRF` refreshes on either controller
+    pub fn refresh_count(&self) -> usize {
+        self.refreshes
+    }
+
+    /// Color index currently on the glass at physical landscape coordinates
+    ///
+    /// This is the raw index, which need not be a valid [`Color`] if the
+    /// controller was sent bad frame data.
+    ///
+    /// `x` is in 0..1600 and `y` in 0..1200, laid out as
+    /// [`Orientation::LANDSCAPE`]. These match
+    /// [`InkyDisplay::set_pixel`](crate::InkyDisplay::set_pixel) only for a
+    /// display left in that orientation; use
+    /// [`oriented_pixel`](Self::oriented_pixel) for any other.
+    ///
+    /// # Panics
+    /// Panics if the coordinates are out of bounds.
+    pub fn pixel(&self, x: usize, y: usize) -> u8 {
+        self.oriented_pixel(Orientation::LANDSCAPE, x, y)
+    }
+
+    /// Color index currently on the glass at logical coordinates of a
+    /// display set to `orientation`
+    ///
+    /// # Example
+    /// ```
+    /// use inky_el133::{InkyDisplay, Orientation, Rotation, colors};
+    /// use inky_el133::emulator::PanelEmulator;
+    ///
+    /// let portrait = Orientation::new(Rotation::Deg90);
+    /// let mut display = InkyDisplay::with_transport(PanelEmulator::new())?;
+    /// display.set_orientation(portrait);
+    /// display.set_pixel(10, 1500, colors::RED)?;
+    /// display.show()?;
+    ///
+    /// let panel = display.transport();
+    /// assert_eq!(panel.oriented_pixel(portrait, 10, 1500), colors::RED.index());
+    /// # Ok::<(), inky_el133::InkyError>(())
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the coordinates are out of bounds for `orientation`.
+    pub fn oriented_pixel(&self, orientation: Orientation, x: usize, y: usize) -> u8 {
+        assert!(
+            x < orientation.width() && y < orientation.height(),
+            "pixel ({}, {}) out of bounds",
+            x,
+            y
+        );
+
+        // Pick the controller by native column
+        let (row, col) = orientation.native_position(x, y);
+        if col < SPLIT_COL {
+            self.controllers[0].glass_pixel(row, col)
+        } else {
+            self.controllers[1].glass_pixel(row, col - SPLIT_COL)
+        }
+    }
+
+    /// Glass contents in landscape row-major order (1600×1200 color indices)
+    pub fn glass(&self) -> Vec<u8> {
+        let mut pixels = Vec::with_capacity(WIDTH * HEIGHT);
+        for y in 0..HEIGHT {
+            for x in 0..WIDTH {
+                pixels.push(self.pixel(x, y));
+            }
+        }
+        pixels
+    }
+
+    /// Encode the glass contents as an RGB PNG
+    pub fn write_png<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut rgb = Vec::with_capacity(WIDTH * HEIGHT * 3);
+        for color in self.glass() {
+            rgb.extend_from_slice(&Color::try_from(color).map_or(INVALID_RGB, Color::rgb));
+        }
+
+        let mut encoder = png::Encoder::new(writer, WIDTH as u32, HEIGHT as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&rgb)?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// Write the glass contents to a PNG file
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let file = std::fs::File::create(path)?;
+        self.write_png(io::BufWriter::new(file))
+    }
+}
+
+impl Transport for PanelEmulator {
+    fn reset(&mut self) -> Result<()> {
+        // Reset clears controller state but the glass keeps its image
+        for controller in &mut self.controllers {
+            let glass = std::mem::take(&mut controller.glass);
+            *controller = Controller::new(controller.cs);
+            controller.glass = glass;
+        }
+        Ok(())
+    }
+
+    fn begin_command(&mut self, cs: ChipSelect, cmd: u8) -> Result<()> {
+        self.pending = Some((cs, cmd, Vec::new()));
+        Ok(())
+    }
+
+    fn write_data(&mut self, bytes: &[u8]) -> Result<()> {
+        if let Some((_, _, data)) = &mut self.pending {
+            data.extend_from_slice(bytes);
+        }
+        Ok(())
+    }
+
+    fn end_command(&mut self) -> Result<()> {
+        let Some((cs, cmd, data)) = self.pending.take() else {
+            return Ok(());
+        };
+
+        let targets: &mut [Controller] = match cs {
+            ChipSelect::CS0 => &mut self.controllers[..1],
+            ChipSelect::CS1 => &mut self.controllers[1..],
+            ChipSelect::Both => &mut self.controllers[..],
+        };
+
+        let mut refreshed = false;
+        for controller in targets {
+            refreshed |= controller.command(cmd, &data, &mut self.violations);
+        }
+        if refreshed {
+            self.refreshes += 1;
+        }
+
+        Ok(())
+    }
+
+    fn wait_busy(&mut self, _timeout_ms: u64) -> Result<Option<Duration>> {
+        Ok(Some(Duration::ZERO))
+    }
+
+    fn is_busy(&mut self) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn delay_ms(&mut self, _ms: u64) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InkyDisplay, Rotation};
+
+    /// Emulator after reset and the two commands it checks for init
+    fn initialized() -> PanelEmulator {
+        let mut panel = PanelEmulator::new();
+        panel.reset().unwrap();
+        panel
+            .send_command(ChipSelect::Both, CMD_PSR, &[0xDF, 0x69])
+            .unwrap();
+        panel
+            .send_command(ChipSelect::Both, CMD_TRES, &[0x04, 0xB0, 0x03, 0x20])
+            .unwrap();
+        panel
+    }
+
+    #[test]
+    fn short_frame_is_a_data_length_violation() {
+        let mut panel = initialized();
+        panel
+            .send_command(ChipSelect::CS1, CMD_DTM, &[0; 10])
+            .unwrap();
+
+        assert_eq!(
+            panel.violations(),
+            [Violation::DataLength {
+                cs: ChipSelect::CS1,
+                expected: FRAME_BYTES,
+                actual: 10,
+            }]
+        );
+    }
+
+    #[test]
+    fn refresh_before_power_on_leaves_glass_alone() {
+        let mut panel = initialized();
+        panel
+            .send_command(ChipSelect::Both, CMD_DRF, &[0x00])
+            .unwrap();
+
+        assert_eq!(
+            panel.violations(),
+            [
+                Violation::RefreshBeforePowerOn {
+                    cs: ChipSelect::CS0
+                },
+                Violation::RefreshBeforePowerOn {
+                    cs: ChipSelect::CS1
+                },
+            ]
+        );
+        assert_eq!(panel.refresh_count(), 0);
+    }
+
+    #[test]
+    fn refresh_without_init_leaves_glass_alone() {
+        let mut panel = PanelEmulator::new();
+        panel.reset().unwrap();
+        panel.send_command(ChipSelect::Both, CMD_PON, &[]).unwrap();
+        panel
+            .send_command(ChipSelect::Both, CMD_DRF, &[0x00])
+            .unwrap();
+
+        assert_eq!(
+            panel.violations(),
+            [
+                Violation::RefreshWithoutInit {
+                    cs: ChipSelect::CS0
+                },
+                Violation::RefreshWithoutInit {
+                    cs: ChipSelect::CS1
+                },
+            ]
+        );
+        assert_eq!(panel.refresh_count(), 0);
+    }
+
+    #[test]
+    fn oriented_pixel_follows_set_pixel() {
+        let orientation = Orientation::new(Rotation::Deg270).mirror_vertical(true);
+        let mut display = InkyDisplay::with_transport(PanelEmulator::new()).unwrap();
+        display.set_orientation(orientation);
+        display.set_pixel(1, 2, Color::Green).unwrap();
+        display.show().unwrap();
+
+        let panel = display.transport();
+        assert_eq!(
+            panel.oriented_pixel(orientation, 1, 2),
+            Color::Green.index()
+        );
+        assert_eq!(
+            panel.oriented_pixel(orientation, 2, 1),
+            Color::White.index()
+        );
+        // Landscape coordinates name a different spot on the glass
+        assert_eq!(panel.pixel(1, 2), Color::White.index());
+    }
+}
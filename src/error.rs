@@ -1,32 +1,77 @@
 use thiserror::Error;
 
+use crate::timing::Phase;
+
 /// Errors that can occur when using the Inky display.
 #[derive(Debug, Error)]
 pub enum InkyError {
-    #[cfg(target_os = "linux")]
+    #[cfg(all(feature = "std", target_os = "linux"))]
     #[error("GPIO error: {0}")]
     Gpio(#[from] gpio_cdev::Error),
 
-    #[cfg(target_os = "linux")]
+    #[cfg(all(feature = "std", target_os = "linux"))]
     #[error("SPI error: {0}")]
     Spi(#[from] std::io::Error),
 
+    #[cfg(feature = "embedded-hal")]
+    #[error("SPI bus error: {0}")]
+    HalSpi(embedded_hal::spi::ErrorKind),
+
+    #[cfg(feature = "embedded-hal")]
+    #[error("GPIO pin error: {0}")]
+    HalPin(embedded_hal::digital::ErrorKind),
+
     #[error("Invalid color index: {0} (valid: 0, 1, 2, 3, 5, 6)")]
     InvalidColor(u8),
 
     #[error("Coordinates out of bounds: ({0}, {1})")]
     OutOfBounds(usize, usize),
 
-    #[error("Display busy timeout")]
-    BusyTimeout,
+    #[error("Display busy timeout during {0}")]
+    BusyTimeout(Phase),
+
+    #[error("Display not connected (BUSY never driven low)")]
+    NotConnected,
+
+    #[error("Refresh cancelled")]
+    Cancelled,
 
     #[error("Invalid pixel buffer size")]
     InvalidBufferSize,
 
-    #[cfg(not(target_os = "linux"))]
+    #[cfg(feature = "std")]
+    #[error("SPI chunk size must be non-zero")]
+    ZeroChunkSize,
+
+    #[cfg(feature = "image")]
+    #[error("Image is {0}×{1} pixels, expected {2}×{3}")]
+    ImageSize(u32, u32, u32, u32),
+
+    #[cfg(feature = "image")]
+    #[error("Image is {0}×{1} pixels; an empty image cannot be fitted")]
+    EmptyImage(u32, u32),
+
+    #[cfg(feature = "image")]
+    #[error("Image error: {0}")]
+    Image(#[from] image::ImageError),
+
+    #[cfg(feature = "image")]
+    #[error("PNG decoding error: {0}")]
+    Png(#[from] png::DecodingError),
+
+    #[cfg(feature = "image")]
+    #[error("PNG palette index {0} out of range ({1} entries)")]
+    PaletteIndex(u8, usize),
+
+    #[cfg(not(all(feature = "std", target_os = "linux")))]
     #[error("This library only works on Linux")]
     UnsupportedPlatform,
 }
 
+/// Error returned when parsing a [`Color`](crate::Color) from a string fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("Unknown color (expected a name such as \"red\" or a hex value such as \"#FF0000\")")]
+pub struct ParseColorError;
+
 /// Convenience type alias for Results with [`InkyError`].
-pub type Result<T> = std::result::Result<T, InkyError>;
+pub type Result<T> = core::result::Result<T, InkyError>;
@@ -0,0 +1,289 @@
+//! Fitting images of any size and aspect ratio to the panel.
+//!
+//! [`Resize`] scales an image to 1600×1200 with one of three [`Fit`] modes,
+//! optionally for a panel mounted in portrait. [`decode`] and [`open`] load
+//! an image and apply its EXIF orientation first, so photos come out upright.
+//!
+//! # Example
+//! ```
+//! use image::{DynamicImage, Rgb, RgbImage};
+//! use inky_el133::fit::{Fit, Resize};
+//! use inky_el133::{Canvas, Color, Palette};
+//!
+//! // A square red image, letterboxed onto a black background
+//! let image = DynamicImage::ImageRgb8(RgbImage::from_pixel(300, 300, Rgb([255, 0, 0])));
+//!
+//! let mut canvas = Canvas::new();
+//! let resize = Resize::new(Fit::Contain(Color::Black));
+//! canvas.load_resized(&image, &resize, &Palette::default())?;
+//!
+//! assert_eq!(canvas.get_pixel(0, 600), Some(Color::Black));
+//! assert_eq!(canvas.get_pixel(800, 600), Some(Color::Red));
+//! # Ok::<(), inky_el133::InkyError>(())
+//! ```
+
+use std::io::Cursor;
+use std::path::Path;
+
+use image::imageops::{self, FilterType};
+use image::{DynamicImage, ImageDecoder, ImageError, ImageReader, Rgb, RgbImage};
+
+use crate::constants::{HEIGHT, WIDTH};
+use crate::error::{InkyError, Result};
+use crate::{Canvas, Color, Palette};
+
+/// How an image is scaled to the panel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fit {
+    /// Scale to fit entirely inside the panel, filling the bars left over with
+    /// a palette color (letterbox or pillarbox)
+    Contain(Color),
+    /// Scale to cover the whole panel, cropping the overflow around a [`Focus`]
+    Cover(Focus),
+    /// Scale each axis independently to the panel size, distorting the image
+    /// unless it already has the panel's aspect ratio
+    Stretch,
+}
+
+/// Point of an image that [`Fit::Cover`] keeps in view
+///
+/// Coordinates are fractions of the image's width and height, from `0.0` (left
+/// or top edge) to `1.0` (right or bottom edge). The crop is centred on the
+/// point as far as the image allows, so the gravity constants such as
+/// [`Focus::TOP`] keep that edge of the image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Focus {
+    x: f32,
+    y: f32,
+}
+
+impl Focus {
+    /// Centre of the image
+    pub const CENTER: Focus = Focus { x: 0.5, y: 0.5 };
+    /// Top edge, centred horizontally
+    pub const TOP: Focus = Focus { x: 0.5, y: 0.0 };
+    /// Bottom edge, centred horizontally
+    pub const BOTTOM: Focus = Focus { x: 0.5, y: 1.0 };
+    /// Left edge, centred vertically
+    pub const LEFT: Focus = Focus { x: 0.0, y: 0.5 };
+    /// Right edge, centred vertically
+    pub const RIGHT: Focus = Focus { x: 1.0, y: 0.5 };
+    /// Top left corner
+    pub const TOP_LEFT: Focus = Focus { x: 0.0, y: 0.0 };
+    /// Top right corner
+    pub const TOP_RIGHT: Focus = Focus { x: 1.0, y: 0.0 };
+    /// Bottom left corner
+    pub const BOTTOM_LEFT: Focus = Focus { x: 0.0, y: 1.0 };
+    /// Bottom right corner
+    pub const BOTTOM_RIGHT: Focus = Focus { x: 1.0, y: 1.0 };
+
+    /// Focal point at fractions `x` and `y` of the image size, clamped to `0.0..=1.0`
+    pub fn point(x: f32, y: f32) -> Self {
+        Self {
+            x: x.clamp(0.0, 1.0),
+            y: y.clamp(0.0, 1.0),
+        }
+    }
+}
+
+impl Default for Focus {
+    fn default() -> Self {
+        Self::CENTER
+    }
+}
+
+/// Scales images to the panel with a [`Fit`] mode
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Resize {
+    fit: Fit,
+    portrait: bool,
+    filter: FilterType,
+}
+
+impl Resize {
+    /// Landscape resize with `fit`, using Lanczos filtering
+    pub const fn new(fit: Fit) -> Self {
+        Self {
+            fit,
+            portrait: false,
+            filter: FilterType::Lanczos3,
+        }
+    }
+
+    /// Fit the image to a panel mounted in portrait (default `false`)
+    ///
+    /// The image is fitted to 1200×1600 and turned a quarter turn
+    /// counter-clockwise onto the canvas, so its top edge lands on the
+    /// canvas's left edge. That is upright on a panel turned a quarter turn
+    /// clockwise. For a display with a portrait
+    /// [`Orientation`](crate::Orientation), whose canvas is already
+    /// 1200×1600, leave this off.
+    pub const fn portrait(mut self, portrait: bool) -> Self {
+        self.portrait = portrait;
+        self
+    }
+
+    /// Resampling filter (default [`FilterType::Lanczos3`])
+    pub const fn filter(mut self, filter: FilterType) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Scale `image` to a 1600×1200 RGB image
+    ///
+    /// `palette` supplies the RGB value of the [`Fit::Contain`] background,
+    /// so matching the result against the same palette gives exactly that
+    /// color in the bars. An image with no pixels fails with
+    /// [`InkyError::EmptyImage`].
+    pub fn apply(&self, image: &DynamicImage, palette: &Palette) -> Result<RgbImage> {
+        self.apply_to(image, WIDTH as u32, HEIGHT as u32, palette)
+    }
+
+    /// Scale `image` to a `width`×`height` RGB image
+    ///
+    /// With [`portrait`](Self::portrait), the image is fitted to
+    /// `height`×`width` and then turned onto the result.
+    pub fn apply_to(
+        &self,
+        image: &DynamicImage,
+        width: u32,
+        height: u32,
+        palette: &Palette,
+    ) -> Result<RgbImage> {
+        if image.width() == 0 || image.height() == 0 {
+            return Err(InkyError::EmptyImage(image.width(), image.height()));
+        }
+
+        let (width, height) = if self.portrait {
+            (height, width)
+        } else {
+            (width, height)
+        };
+
+        let fitted = match self.fit {
+            Fit::Contain(background) => self.contain(image, width, height, palette.rgb(background)),
+            Fit::Cover(focus) => self.cover(image, width, height, focus),
+            Fit::Stretch => image.resize_exact(width, height, self.filter).into_rgb8(),
+        };
+
+        Ok(if self.portrait {
+            imageops::rotate270(&fitted)
+        } else {
+            fitted
+        })
+    }
+
+    fn contain(
+        &self,
+        image: &DynamicImage,
+        width: u32,
+        height: u32,
+        background: [u8; 3],
+    ) -> RgbImage {
+        let scale = f64::min(
+            width as f64 / image.width() as f64,
+            height as f64 / image.height() as f64,
+        );
+        let scaled_width = ((image.width() as f64 * scale).round() as u32).clamp(1, width);
+        let scaled_height = ((image.height() as f64 * scale).round() as u32).clamp(1, height);
+        let scaled = image
+            .resize_exact(scaled_width, scaled_height, self.filter)
+            .into_rgb8();
+
+        let mut fitted = RgbImage::from_pixel(width, height, Rgb(background));
+        let x = (width - scaled_width) / 2;
+        let y = (height - scaled_height) / 2;
+        imageops::replace(&mut fitted, &scaled, x.into(), y.into());
+        fitted
+    }
+
+    fn cover(&self, image: &DynamicImage, width: u32, height: u32, focus: Focus) -> RgbImage {
+        // Crop the source to the panel's aspect ratio, then scale the crop
+        let scale = f64::max(
+            width as f64 / image.width() as f64,
+            height as f64 / image.height() as f64,
+        );
+        let crop_width = ((width as f64 / scale).round() as u32).clamp(1, image.width());
+        let crop_height = ((height as f64 / scale).round() as u32).clamp(1, image.height());
+        let offset = |size: u32, crop: u32, focus: f32| {
+            let centred = (focus as f64 * size as f64 - crop as f64 / 2.0).round();
+            centred.clamp(0.0, (size - crop) as f64) as u32
+        };
+        let x = offset(image.width(), crop_width, focus.x);
+        let y = offset(image.height(), crop_height, focus.y);
+
+        image
+            .crop_imm(x, y, crop_width, crop_height)
+            .resize_exact(width, height, self.filter)
+            .into_rgb8()
+    }
+}
+
+impl Default for Resize {
+    fn default() -> Self {
+        Self::new(Fit::Cover(Focus::CENTER))
+    }
+}
+
+impl Canvas {
+    /// Scale an image of any size to the canvas with `resize`, then map each
+    /// pixel to its nearest palette color
+    pub fn load_resized(
+        &mut self,
+        image: &DynamicImage,
+        resize: &Resize,
+        palette: &Palette,
+    ) -> Result<()> {
+        let (width, height) = (self.width() as u32, self.height() as u32);
+        self.load_rgb_image(&resize.apply_to(image, width, height, palette)?, palette)
+    }
+}
+
+/// Decode an image in any supported format, turned upright according to its
+/// EXIF orientation
+pub fn decode(data: &[u8]) -> Result<DynamicImage> {
+    let reader = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .map_err(ImageError::IoError)?;
+    upright(reader)
+}
+
+/// Open an image file, turned upright according to its EXIF orientation
+pub fn open(path: impl AsRef<Path>) -> Result<DynamicImage> {
+    let reader = ImageReader::open(path)
+        .and_then(|reader| reader.with_guessed_format())
+        .map_err(ImageError::IoError)?;
+    upright(reader)
+}
+
+fn upright<R: std::io::BufRead + std::io::Seek>(reader: ImageReader<R>) -> Result<DynamicImage> {
+    let mut decoder = reader.into_decoder()?;
+    let orientation = decoder.orientation()?;
+    let mut image = DynamicImage::from_decoder(decoder)?;
+    image.apply_orientation(orientation);
+    Ok(image)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_image_is_an_error_in_every_mode() {
+        let fits = [
+            Fit::Contain(Color::White),
+            Fit::Cover(Focus::CENTER),
+            Fit::Stretch,
+        ];
+        for (width, height) in [(0, 10), (10, 0), (0, 0)] {
+            let image = DynamicImage::new_rgb8(width, height);
+            for fit in fits {
+                let result = Resize::new(fit).apply(&image, &Palette::default());
+                assert!(
+                    matches!(result, Err(InkyError::EmptyImage(w, h)) if (w, h) == (width, height)),
+                    "{fit:?} {width}×{height}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! [`embedded-graphics`](https://docs.rs/embedded-graphics) support.
+//!
+//! [`Color`] is an embedded-graphics [`PixelColor`] and [`Canvas`] is a
+//! [`DrawTarget`], so primitives, fonts and images from the embedded-graphics
+//! ecosystem draw straight onto a frame. Colors from other color spaces are
+//! converted to the closest of the six inks in [`Palette::NOMINAL`].
+//!
+//! # Example
+//! ```
+//! use embedded_graphics::mono_font::{MonoTextStyle, ascii::FONT_10X20};
+//! use embedded_graphics::prelude::*;
+//! use embedded_graphics::primitives::{Circle, PrimitiveStyle, Rectangle};
+//! use embedded_graphics::text::Text;
+//! use inky_el133::{Canvas, Color};
+//!
+//! let mut canvas = Canvas::new();
+//!
+//! Rectangle::new(Point::new(100, 100), Size::new(400, 200))
+//!     .into_styled(PrimitiveStyle::with_fill(Color::Blue))
+//!     .draw(&mut canvas)?;
+//! Circle::new(Point::new(800, 400), 300)
+//!     .into_styled(PrimitiveStyle::with_stroke(Color::Red, 5))
+//!     .draw(&mut canvas)?;
+//! let style = MonoTextStyle::new(&FONT_10X20, Color::White);
+//! Text::new("Hello, Spectra 6", Point::new(120, 160), style).draw(&mut canvas)?;
+//!
+//! assert_eq!(canvas.get_pixel(101, 101), Some(Color::Blue));
+//! # Ok::<(), core::convert::Infallible>(())
+//! ```
+
+use core::convert::Infallible;
+
+use embedded_graphics_core::Pixel;
+use embedded_graphics_core::draw_target::DrawTarget;
+use embedded_graphics_core::geometry::{Dimensions, OriginDimensions, Size};
+use embedded_graphics_core::pixelcolor::{
+    BinaryColor, Gray8, PixelColor, Rgb555, Rgb565, Rgb888, RgbColor,
+};
+use embedded_graphics_core::primitives::Rectangle;
+
+use crate::{Canvas, Color, Palette};
+
+impl PixelColor for Color {
+    type Raw = ();
+}
+
+/// `On` draws black ink, `Off` leaves the paper white
+impl From<BinaryColor> for Color {
+    fn from(color: BinaryColor) -> Self {
+        match color {
+            BinaryColor::On => Color::Black,
+            BinaryColor::Off => Color::White,
+        }
+    }
+}
+
+impl From<Rgb888> for Color {
+    fn from(color: Rgb888) -> Self {
+        Palette::NOMINAL.nearest([color.r(), color.g(), color.b()])
+    }
+}
+
+impl From<Rgb565> for Color {
+    fn from(color: Rgb565) -> Self {
+        Rgb888::from(color).into()
+    }
+}
+
+impl From<Rgb555> for Color {
+    fn from(color: Rgb555) -> Self {
+        Rgb888::from(color).into()
+    }
+}
+
+impl From<Gray8> for Color {
+    fn from(color: Gray8) -> Self {
+        Rgb888::from(color).into()
+    }
+}
+
+impl From<Color> for Rgb888 {
+    fn from(color: Color) -> Self {
+        let [r, g, b] = color.rgb();
+        Rgb888::new(r, g, b)
+    }
+}
+
+impl OriginDimensions for Canvas {
+    fn size(&self) -> Size {
+        Size::new(self.width() as u32, self.height() as u32)
+    }
+}
+
+/// Pixels outside the canvas are clipped
+impl DrawTarget for Canvas {
+    type Color = Color;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if let (Ok(x), Ok(y)) = (usize::try_from(point.x), usize::try_from(point.y)) {
+                // Out of bounds is the only possible error; clip silently
+                let _ = self.set_pixel(x, y, color);
+            }
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        if area.is_zero_sized() {
+            return Ok(());
+        }
+
+        let stride = self.width();
+        let x = area.top_left.x as usize;
+        let width = area.size.width as usize;
+        for y in area.rows() {
+            let start = y as usize * stride + x;
+            self.as_mut_slice()[start..start + width].fill(color);
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.fill(color);
+        Ok(())
+    }
+}
@@ -0,0 +1,304 @@
+//! [`embedded-hal`](embedded_hal) 1.0 backend for microcontrollers.
+//!
+//! [`HalTransport`] drives the EL133UF1 from any HAL that implements the
+//! `embedded-hal` 1.0 traits, so the same init and refresh sequence used on a
+//! Raspberry Pi runs on an RP2040, ESP32 and friends. Pins are passed in as
+//! HAL objects instead of the Linux GPIO numbers used by
+//! [`DisplayController`](crate::DisplayController).
+//!
+//! The panel has two chip selects that are toggled by the driver itself, so
+//! the [`SpiDevice`] given here should not manage a chip select of its own
+//! (for example `embedded_hal_bus::spi::ExclusiveDevice` with `NoCs`).
+
+use core::time::Duration;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{self, InputPin, OutputPin};
+use embedded_hal::spi::{self, SpiDevice};
+
+use crate::constants::{RESET_PULSE_MS, SPI_CHUNK_SIZE};
+use crate::error::{InkyError, Result};
+use crate::transport::{ChipSelect, Transport};
+
+/// Interval between BUSY line polls
+const BUSY_POLL_MS: u32 = 10;
+
+/// GPIO lines wired to the display
+pub struct HalPins<CS0, CS1, DC, RST, BUSY> {
+    pub cs0: CS0,
+    pub cs1: CS1,
+    pub dc: DC,
+    pub reset: RST,
+    pub busy: BUSY,
+}
+
+/// [`Transport`] built on `embedded-hal` 1.0 SPI, GPIO and delay traits.
+///
+/// # Example
+/// ```
+/// use embedded_hal_mock::eh1::delay::NoopDelay;
+/// use embedded_hal_mock::eh1::digital::{Mock as Pin, State, Transaction as PinT};
+/// use embedded_hal_mock::eh1::spi::{Mock as Spi, Transaction as SpiT};
+/// use inky_el133::hal::{HalPins, HalTransport};
+/// use inky_el133::{ChipSelect, Transport};
+///
+/// let spi = Spi::new(&[
+///     SpiT::transaction_start(),
+///     SpiT::write_vec(vec![0x04]),
+///     SpiT::transaction_end(),
+/// ]);
+/// let pins = HalPins {
+///     cs0: Pin::new(&[PinT::set(State::Low), PinT::set(State::High)]),
+///     cs1: Pin::new(&[PinT::set(State::High)]),
+///     dc: Pin::new(&[PinT::set(State::Low), PinT::set(State::Low)]),
+///     reset: Pin::new(&[PinT::set(State::Low), PinT::set(State::High)]),
+///     busy: Pin::new(&[PinT::get(State::High), PinT::get(State::Low)]),
+/// };
+///
+/// let mut transport = HalTransport::new(spi, pins, NoopDelay::new());
+/// transport.reset()?;
+/// transport.send_command(ChipSelect::CS0, 0x04, &[])?;
+/// let busy = transport.wait_busy(200)?;
+/// assert_eq!(busy.map(|d| d.as_millis()), Some(10));
+///
+/// let (mut spi, mut pins, _) = transport.release();
+/// spi.done();
+/// pins.cs0.done();
+/// pins.cs1.done();
+/// pins.dc.done();
+/// pins.reset.done();
+/// pins.busy.done();
+/// # Ok::<(), inky_el133::InkyError>(())
+/// ```
+pub struct HalTransport<SPI, CS0, CS1, DC, RST, BUSY, D> {
+    spi: SPI,
+    pins: HalPins<CS0, CS1, DC, RST, BUSY>,
+    delay: D,
+}
+
+impl<SPI, CS0, CS1, DC, RST, BUSY, D> HalTransport<SPI, CS0, CS1, DC, RST, BUSY, D>
+where
+    SPI: SpiDevice,
+    CS0: OutputPin,
+    CS1: OutputPin,
+    DC: OutputPin,
+    RST: OutputPin,
+    BUSY: InputPin,
+    D: DelayNs,
+{
+    /// Create a transport from an SPI device, the display pins and a delay source
+    pub fn new(spi: SPI, pins: HalPins<CS0, CS1, DC, RST, BUSY>, delay: D) -> Self {
+        Self { spi, pins, delay }
+    }
+
+    /// Give back the SPI device, pins and delay
+    pub fn release(self) -> (SPI, HalPins<CS0, CS1, DC, RST, BUSY>, D) {
+        (self.spi, self.pins, self.delay)
+    }
+}
+
+fn pin<E: digital::Error>(err: E) -> InkyError {
+    InkyError::HalPin(err.kind())
+}
+
+fn bus<E: spi::Error>(err: E) -> InkyError {
+    InkyError::HalSpi(err.kind())
+}
+
+impl<SPI, CS0, CS1, DC, RST, BUSY, D> Transport for HalTransport<SPI, CS0, CS1, DC, RST, BUSY, D>
+where
+    SPI: SpiDevice,
+    CS0: OutputPin,
+    CS1: OutputPin,
+    DC: OutputPin,
+    RST: OutputPin,
+    BUSY: InputPin,
+    D: DelayNs,
+{
+    /// Perform hardware reset of the display (inky_el133uf1.py:229-232)
+    fn reset(&mut self) -> Result<()> {
+        self.set_reset(true)?;
+        self.delay.delay_ms(RESET_PULSE_MS as u32);
+
+        self.set_reset(false)?;
+        self.delay.delay_ms(RESET_PULSE_MS as u32);
+
+        Ok(())
+    }
+
+    /// RESET is active low
+    fn set_reset(&mut self, asserted: bool) -> Result<()> {
+        if asserted {
+            self.pins.reset.set_low().map_err(pin)
+        } else {
+            self.pins.reset.set_high().map_err(pin)
+        }
+    }
+
+    /// Select the controller(s) and send a command byte
+    fn begin_command(&mut self, cs: ChipSelect, cmd: u8) -> Result<()> {
+        match cs {
+            ChipSelect::CS0 => self.pins.cs0.set_low().map_err(pin)?,
+            ChipSelect::CS1 => self.pins.cs1.set_low().map_err(pin)?,
+            ChipSelect::Both => {
+                self.pins.cs0.set_low().map_err(pin)?;
+                self.pins.cs1.set_low().map_err(pin)?;
+            }
+        }
+
+        self.pins.dc.set_low().map_err(pin)?;
+        self.spi.write(&[cmd]).map_err(bus)
+    }
+
+    /// Send data for the current command in 4KB transactions (inky_el133uf1.py:367-370)
+    fn write_data(&mut self, data: &[u8]) -> Result<()> {
+        self.pins.dc.set_high().map_err(pin)?;
+
+        for chunk in data.chunks(SPI_CHUNK_SIZE) {
+            self.spi.write(chunk).map_err(bus)?;
+        }
+
+        Ok(())
+    }
+
+    /// Deselect both controllers
+    fn end_command(&mut self) -> Result<()> {
+        self.pins.cs0.set_high().map_err(pin)?;
+        self.pins.cs1.set_high().map_err(pin)?;
+        self.pins.dc.set_low().map_err(pin)
+    }
+
+    /// Wait for busy pin to go low (display ready) - inky_el133uf1.py:261-270
+    ///
+    /// There is no clock in `embedded-hal`, so the returned duration counts
+    /// 10 ms poll intervals.
+    fn wait_busy(&mut self, timeout_ms: u64) -> Result<Option<Duration>> {
+        let mut waited_ms = 0u64;
+        while self.pins.busy.is_high().map_err(pin)? {
+            if waited_ms >= timeout_ms {
+                return Ok(None);
+            }
+            self.delay.delay_ms(BUSY_POLL_MS);
+            waited_ms += BUSY_POLL_MS as u64;
+        }
+
+        Ok(Some(Duration::from_millis(waited_ms)))
+    }
+
+    fn is_busy(&mut self) -> Result<bool> {
+        self.pins.busy.is_high().map_err(pin)
+    }
+
+    fn delay_ms(&mut self, ms: u64) {
+        self.delay.delay_ms(ms.min(u32::MAX as u64) as u32);
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::digital::{Mock as Pin, State, Transaction as PinT};
+    use embedded_hal_mock::eh1::spi::{Mock as Spi, Transaction as SpiT};
+
+    use super::*;
+    use crate::constants::*;
+    use crate::{INIT_SEQUENCE, InkyDisplay};
+
+    /// Transactions expected on the bus and on each pin, in order
+    #[derive(Default)]
+    struct Expected {
+        spi: Vec<SpiT<u8>>,
+        cs0: Vec<PinT>,
+        cs1: Vec<PinT>,
+        dc: Vec<PinT>,
+        busy: Vec<PinT>,
+    }
+
+    impl Expected {
+        fn write(&mut self, bytes: &[u8]) {
+            self.spi.extend([
+                SpiT::transaction_start(),
+                SpiT::write_vec(bytes.to_vec()),
+                SpiT::transaction_end(),
+            ]);
+        }
+
+        fn command(&mut self, cs: ChipSelect, cmd: u8, data: &[u8]) {
+            if cs != ChipSelect::CS1 {
+                self.cs0.push(PinT::set(State::Low));
+            }
+            if cs != ChipSelect::CS0 {
+                self.cs1.push(PinT::set(State::Low));
+            }
+            self.dc.push(PinT::set(State::Low));
+            self.write(&[cmd]);
+
+            if !data.is_empty() {
+                self.dc.push(PinT::set(State::High));
+                for chunk in data.chunks(SPI_CHUNK_SIZE) {
+                    self.write(chunk);
+                }
+            }
+
+            self.cs0.push(PinT::set(State::High));
+            self.cs1.push(PinT::set(State::High));
+            self.dc.push(PinT::set(State::Low));
+        }
+
+        /// BUSY reads high `polls` times, then releases
+        fn busy(&mut self, polls: usize) {
+            self.busy.extend((0..polls).map(|_| PinT::get(State::High)));
+            self.busy.push(PinT::get(State::Low));
+        }
+    }
+
+    #[test]
+    fn initialize_and_show_drive_the_bus_in_order() {
+        let mut expected = Expected::default();
+
+        // Idle before the reset, then released straight after it
+        expected.busy(0);
+        expected.busy(0);
+        for (cs, cmd, data) in INIT_SEQUENCE {
+            expected.command(cs, cmd, data);
+        }
+
+        // A white frame to each half, then the refresh
+        let white = vec![0x11; HALF_BYTES];
+        expected.command(ChipSelect::CS0, CMD_DTM, &white);
+        expected.command(ChipSelect::CS1, CMD_DTM, &white);
+        expected.command(ChipSelect::Both, CMD_PON, &[]);
+        expected.busy(0);
+        expected.command(ChipSelect::Both, CMD_DRF, &[0x00]);
+        expected.busy(3);
+        expected.command(ChipSelect::Both, CMD_POF, &[0x00]);
+        expected.busy(0);
+
+        let pins = HalPins {
+            cs0: Pin::new(&expected.cs0),
+            cs1: Pin::new(&expected.cs1),
+            dc: Pin::new(&expected.dc),
+            reset: Pin::new(&[PinT::set(State::Low), PinT::set(State::High)]),
+            busy: Pin::new(&expected.busy),
+        };
+        let transport = HalTransport::new(Spi::new(&expected.spi), pins, NoopDelay::new());
+
+        let mut display = InkyDisplay::with_transport(transport).unwrap();
+        display.show().unwrap();
+        assert_eq!(
+            display.last_busy_times().refresh,
+            Duration::from_millis(3 * BUSY_POLL_MS as u64)
+        );
+
+        let (mut spi, mut pins, _) = display.into_transport().release();
+        spi.done();
+        pins.cs0.done();
+        pins.cs1.done();
+        pins.dc.done();
+        pins.reset.done();
+        pins.busy.done();
+    }
+}
@@ -0,0 +1,228 @@
+//! [`image`](https://docs.rs/image) crate integration.
+//!
+//! Loads images into a [`Canvas`] by matching every pixel against a
+//! [`Palette`], and exports a canvas back to an [`RgbImage`].
+
+use std::io::Cursor;
+
+use image::{DynamicImage, RgbImage};
+use png::{BitDepth, ColorType};
+
+use crate::error::{InkyError, Result};
+use crate::{Canvas, Color, Palette, Preview};
+
+impl Canvas {
+    /// Load an image the size of the canvas, mapping each pixel to its nearest
+    /// palette color
+    ///
+    /// # Example
+    /// ```
+    /// use image::{DynamicImage, Rgb, RgbImage};
+    /// use inky_el133::{Canvas, Color, Palette};
+    ///
+    /// let image = RgbImage::from_pixel(1600, 1200, Rgb([240, 20, 10]));
+    ///
+    /// let mut canvas = Canvas::new();
+    /// canvas.load_image(&DynamicImage::ImageRgb8(image), &Palette::default())?;
+    /// assert!(canvas.as_slice().iter().all(|&c| c == Color::Red));
+    /// # Ok::<(), inky_el133::InkyError>(())
+    /// ```
+    pub fn load_image(&mut self, image: &DynamicImage, palette: &Palette) -> Result<()> {
+        match image.as_rgb8() {
+            Some(rgb) => self.load_rgb_image(rgb, palette),
+            None => self.load_rgb_image(&image.to_rgb8(), palette),
+        }
+    }
+
+    /// Load an RGB image the size of the canvas, mapping each pixel to its
+    /// nearest palette color
+    pub fn load_rgb_image(&mut self, image: &RgbImage, palette: &Palette) -> Result<()> {
+        self.check_size(image.width(), image.height())?;
+
+        let matcher = palette.matcher();
+        for (dst, pixel) in self.as_mut_slice().iter_mut().zip(image.pixels()) {
+            *dst = matcher.nearest(pixel.0);
+        }
+        Ok(())
+    }
+
+    /// Load a PNG the size of the canvas
+    ///
+    /// Indexed PNGs are mapped one palette entry at a time, so a PNG whose
+    /// palette already holds the panel colors loads exactly. A pixel indexing
+    /// past the end of the PNG's palette fails with
+    /// [`InkyError::PaletteIndex`] and leaves the canvas unchanged. Any other
+    /// PNG is decoded and matched pixel by pixel like
+    /// [`load_image`](Self::load_image).
+    pub fn load_png(&mut self, data: &[u8], palette: &Palette) -> Result<()> {
+        let mut reader = png::Decoder::new(Cursor::new(data)).read_info()?;
+        let info = reader.info();
+        self.check_size(info.width, info.height)?;
+
+        let (ColorType::Indexed, Some(entries)) = (info.color_type, info.palette.as_deref()) else {
+            let image = image::load_from_memory_with_format(data, image::ImageFormat::Png)?;
+            return self.load_image(&image, palette);
+        };
+
+        // Map each PNG palette entry once
+        let matcher = palette.matcher();
+        let mut lut = [Color::White; 256];
+        for (slot, rgb) in lut.iter_mut().zip(entries.chunks_exact(3)) {
+            *slot = matcher.nearest([rgb[0], rgb[1], rgb[2]]);
+        }
+        let count = entries.len() / 3;
+
+        let bits = match info.bit_depth {
+            BitDepth::One => 1,
+            BitDepth::Two => 2,
+            BitDepth::Four => 4,
+            _ => 8,
+        };
+
+        let size = reader
+            .output_buffer_size()
+            .ok_or(InkyError::InvalidBufferSize)?;
+        let mut buf = vec![0; size];
+        let frame = reader.next_frame(&mut buf)?;
+
+        let per_byte = 8 / bits;
+        let mask = u8::MAX >> (8 - bits);
+        let width = self.width();
+        let indices: Vec<u8> = buf
+            .chunks_exact(frame.line_size)
+            .take(self.height())
+            .flat_map(|row| {
+                (0..width).map(move |x| {
+                    let shift = 8 - bits * (x % per_byte + 1);
+                    (row[x / per_byte] >> shift) & mask
+                })
+            })
+            .collect();
+        if let Some(&index) = indices.iter().find(|&&index| index as usize >= count) {
+            return Err(InkyError::PaletteIndex(index, count));
+        }
+
+        for (dst, index) in self.as_mut_slice().iter_mut().zip(indices) {
+            *dst = lut[index as usize];
+        }
+        Ok(())
+    }
+
+    /// Render the canvas as an RGB image using `palette`
+    ///
+    /// # Example
+    /// ```
+    /// use inky_el133::{Canvas, Color, Palette};
+    ///
+    /// let mut canvas = Canvas::new();
+    /// canvas.set_pixel(5, 5, Color::Green)?;
+    ///
+    /// let image = canvas.to_rgb_image(&Palette::default());
+    /// assert_eq!(image.get_pixel(5, 5).0, [58, 91, 70]);
+    /// # Ok::<(), inky_el133::InkyError>(())
+    /// ```
+    pub fn to_rgb_image(&self, palette: &Palette) -> RgbImage {
+        let raw = self
+            .as_slice()
+            .iter()
+            .flat_map(|&color| palette.rgb(color))
+            .collect();
+        RgbImage::from_raw(self.width() as u32, self.height() as u32, raw)
+            .expect("buffer matches canvas dimensions")
+    }
+}
+
+impl Preview {
+    /// Render `canvas` as an RGB image, ready to save for review
+    ///
+    /// # Example
+    /// ```no_run
+    /// use inky_el133::{Canvas, Preview};
+    ///
+    /// let canvas = Canvas::new();
+    /// Preview::default().scale(2).grid(true).render_image(&canvas).save("preview.png")?;
+    /// # Ok::<(), image::ImageError>(())
+    /// ```
+    pub fn render_image(&self, canvas: &Canvas) -> RgbImage {
+        let (width, height) = self.size(canvas);
+        RgbImage::from_raw(width as u32, height as u32, self.render(canvas))
+            .expect("buffer matches preview dimensions")
+    }
+}
+
+impl Canvas {
+    fn check_size(&self, width: u32, height: u32) -> Result<()> {
+        let expected = (self.width() as u32, self.height() as u32);
+        if (width, height) != expected {
+            return Err(InkyError::ImageSize(width, height, expected.0, expected.1));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{HEIGHT, WIDTH};
+
+    /// Encode a landscape indexed PNG whose palette holds `inks` and whose
+    /// pixel at `(x, y)` has index `index(x, y)`
+    fn indexed_png(depth: BitDepth, inks: &[Color], index: impl Fn(usize, usize) -> u8) -> Vec<u8> {
+        let bits = depth as usize;
+        let line = (WIDTH * bits).div_ceil(8);
+        let mut data = vec![0u8; line * HEIGHT];
+        for y in 0..HEIGHT {
+            for x in 0..WIDTH {
+                let bit = x * bits;
+                data[y * line + bit / 8] |= index(x, y) << (8 - bits - bit % 8);
+            }
+        }
+
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, WIDTH as u32, HEIGHT as u32);
+        encoder.set_color(ColorType::Indexed);
+        encoder.set_depth(depth);
+        encoder.set_palette(inks.iter().flat_map(|ink| ink.rgb()).collect::<Vec<_>>());
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&data).unwrap();
+        writer.finish().unwrap();
+        png
+    }
+
+    #[test]
+    fn indexed_png_loads_exactly_at_every_bit_depth() {
+        let depths = [
+            (BitDepth::One, 2),
+            (BitDepth::Two, 4),
+            (BitDepth::Four, 6),
+            (BitDepth::Eight, 6),
+        ];
+        for (depth, count) in depths {
+            let inks = &Color::ALL[..count];
+            let index = |x: usize, y: usize| ((x + 3 * y) % count) as u8;
+            let png = indexed_png(depth, inks, index);
+
+            let mut canvas = Canvas::new();
+            canvas.load_png(&png, &Palette::NOMINAL).unwrap();
+            for (i, &color) in canvas.as_slice().iter().enumerate() {
+                let (x, y) = (i % WIDTH, i / WIDTH);
+                assert_eq!(color, inks[index(x, y) as usize], "{depth:?} at ({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn indexed_png_past_its_palette_is_an_error() {
+        let png = indexed_png(BitDepth::Four, &Color::ALL, |x, y| {
+            if (x, y) == (800, 600) { 7 } else { 0 }
+        });
+
+        let mut canvas = Canvas::new();
+        canvas.fill(Color::Blue);
+        assert!(matches!(
+            canvas.load_png(&png, &Palette::NOMINAL),
+            Err(InkyError::PaletteIndex(7, 6))
+        ));
+        assert!(canvas.as_slice().iter().all(|&c| c == Color::Blue));
+    }
+}
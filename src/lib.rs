@@ -5,134 +5,644 @@
 //!
 //! # Example
 //! ```no_run
+//! # #[cfg(feature = "std")] {
 //! use inky_el133::{InkyDisplay, colors};
 //!
 //! let mut display = InkyDisplay::new()?;
-//! display.fill(colors::WHITE)?;
+//! display.fill(colors::WHITE);
 //! display.set_pixel(100, 100, colors::RED)?;
 //! display.show()?;
+//! # }
 //! # Ok::<(), inky_el133::InkyError>(())
 //! ```
+//!
+//! # Features
+//! - `std` (default): Linux GPIO/SPI [`DisplayController`]. Without it the
+//!   crate is `#![no_std]`.
+//! - `alloc`: heap-backed [`Canvas`] used by [`InkyDisplay::set_pixel`],
+//!   [`InkyDisplay::fill`] and [`InkyDisplay::show`]. Without it frames are
+//!   streamed with [`InkyDisplay::show_with`] or [`InkyDisplay::show_from_slice`].
+//! - `embedded-hal`: transport for microcontrollers.
+//! - `emulator`: software panel emulator.
+//! - `tokio`: [`InkyDisplay::show_async`] with progress reporting.
+//! - `serde`: `Serialize`/`Deserialize` for [`Color`], by lowercase name.
+//! - `embedded-graphics`: `DrawTarget` for [`Canvas`]; see [`graphics`].
+//! - `image`: load images and PNGs into a [`Canvas`] and export it as an
+//!   `RgbImage`; fit images of any size to the panel with [`fit`].
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use core::time::Duration;
 
-mod buffer;
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use canvas::{FrameSource, PackedFrame};
+
+#[cfg(feature = "tokio")]
+mod async_display;
+#[cfg(feature = "std")]
+mod builder;
+mod cancel;
+#[cfg(feature = "alloc")]
+mod canvas;
+mod color;
+mod colorspace;
 mod constants;
 mod controller;
+#[cfg(feature = "alloc")]
+pub mod dither;
+#[cfg(feature = "emulator")]
+pub mod emulator;
 pub mod error;
+#[cfg(feature = "image")]
+pub mod fit;
+#[cfg(feature = "embedded-graphics")]
+pub mod graphics;
+#[cfg(feature = "embedded-hal")]
+pub mod hal;
+#[cfg(feature = "image")]
+mod imaging;
+#[cfg(feature = "alloc")]
+mod native;
+mod orientation;
+#[cfg(feature = "alloc")]
+mod packed;
+mod palette;
+#[cfg(feature = "alloc")]
+mod preview;
+mod stream;
+mod timing;
+pub mod transport;
 
-use buffer::PixelBuffer;
+#[cfg(feature = "tokio")]
+pub use async_display::Progress;
+#[cfg(feature = "std")]
+pub use builder::InkyDisplayBuilder;
+pub use cancel::CancellationToken;
+#[cfg(feature = "alloc")]
+pub use canvas::Canvas;
+pub use color::Color;
 use constants::*;
-use controller::{ChipSelect, DisplayController};
-pub use error::{InkyError, Result};
+#[cfg(feature = "std")]
+pub use controller::ControllerConfig;
+pub use controller::DisplayController;
+pub use error::{InkyError, ParseColorError, Result};
+#[cfg(feature = "alloc")]
+pub use native::NativeCanvas;
+pub use orientation::{Orientation, Rotation};
+#[cfg(feature = "alloc")]
+pub use packed::PackedCanvas;
+pub use palette::{Metric, Palette};
+#[cfg(feature = "alloc")]
+pub use preview::Preview;
+pub use timing::{BusyTimes, Phase, Timing};
+pub use transport::{ChipSelect, Transport};
 
-/// Color indices for the 6-color Spectra 6 display.
-///
-/// Note: Color index 4 is invalid and skipped by the display hardware.
+/// Shorthand constants for each [`Color`].
 pub mod colors {
-    pub const BLACK: u8 = 0;
-    pub const WHITE: u8 = 1;
-    pub const YELLOW: u8 = 2;
-    pub const RED: u8 = 3;
-    pub const BLUE: u8 = 5;
-    pub const GREEN: u8 = 6;
+    use crate::Color;
+
+    pub const BLACK: Color = Color::Black;
+    pub const WHITE: Color = Color::White;
+    pub const YELLOW: Color = Color::Yellow;
+    pub const RED: Color = Color::Red;
+    pub const BLUE: Color = Color::Blue;
+    pub const GREEN: Color = Color::Green;
+}
+
+/// How often a cancellable busy wait checks its token
+const CANCEL_POLL_MS: u64 = 100;
+
+/// Only `into_transport` takes the transport, and it consumes the display
+const TRANSPORT_TAKEN: &str = "transport is present until into_transport";
+
+/// Commands sent after reset, once BUSY has released (inky_el133uf1.py:236-255)
+const INIT_SEQUENCE: [(ChipSelect, u8, &[u8]); 17] = [
+    (
+        ChipSelect::CS0,
+        CMD_ANTM,
+        &[0xC0, 0x1C, 0x1C, 0xCC, 0xCC, 0xCC, 0x15, 0x15, 0x55],
+    ),
+    (
+        ChipSelect::Both,
+        CMD_CMD66,
+        &[0x49, 0x55, 0x13, 0x5D, 0x05, 0x10],
+    ),
+    (ChipSelect::Both, CMD_PSR, &[0xDF, 0x69]),
+    (ChipSelect::Both, CMD_PLL, &[0x08]),
+    (ChipSelect::Both, CMD_CDI, &[0xF7]),
+    (ChipSelect::Both, CMD_TCON, &[0x03, 0x03]),
+    (ChipSelect::Both, CMD_AGID, &[0x10]),
+    (ChipSelect::Both, CMD_PWS, &[0x22]),
+    (ChipSelect::Both, CMD_CCSET, &[0x01]),
+    (ChipSelect::Both, CMD_TRES, &[0x04, 0xB0, 0x03, 0x20]),
+    (
+        ChipSelect::CS0,
+        CMD_PWR,
+        &[0x0F, 0x00, 0x28, 0x2C, 0x28, 0x38],
+    ),
+    (ChipSelect::CS0, CMD_EN_BUF, &[0x07]),
+    (ChipSelect::CS0, CMD_BTST_P, &[0xD8, 0x18]),
+    (ChipSelect::CS0, CMD_BOOST_VDDP_EN, &[0x01]),
+    (ChipSelect::CS0, CMD_BTST_N, &[0xD8, 0x18]),
+    (ChipSelect::CS0, CMD_BUCK_BOOST_VDDN, &[0x01]),
+    (ChipSelect::CS0, CMD_TFT_VCOM_POWER, &[0x02]),
+];
+
+/// A refresh command sent to both controllers, followed by a BUSY wait
+#[derive(Clone, Copy)]
+struct Step {
+    phase: Phase,
+    cmd: u8,
+    data: &'static [u8],
+}
+
+/// Power on, refresh from controller memory and power off again
+const REFRESH_STEPS: [Step; 3] = [POWER_ON, REFRESH, POWER_OFF];
+
+const POWER_ON: Step = Step {
+    phase: Phase::PowerOn,
+    cmd: CMD_PON,
+    data: &[],
+};
+
+const REFRESH: Step = Step {
+    phase: Phase::Refresh,
+    cmd: CMD_DRF,
+    data: &[0x00],
+};
+
+const POWER_OFF: Step = Step {
+    phase: Phase::PowerOff,
+    cmd: CMD_POF,
+    data: &[0x00],
+};
+
+fn check_cancel(cancel: Option<&CancellationToken>) -> Result<()> {
+    match cancel {
+        Some(cancel) if cancel.is_cancelled() => Err(InkyError::Cancelled),
+        _ => Ok(()),
+    }
 }
 
 /// Main interface for the Inky Impression 13.3" display
-pub struct InkyDisplay {
-    controller: DisplayController,
-    buffer: PixelBuffer,
+///
+/// Generic over the [`Transport`] used to reach the panel; the default is the
+/// GPIO/SPI [`DisplayController`].
+pub struct InkyDisplay<T: Transport = DisplayController> {
+    /// Always `Some` until [`into_transport`](Self::into_transport) takes it
+    controller: Option<T>,
+    timing: Timing,
+    strict: bool,
+    busy_times: BusyTimes,
+    /// Set from `CMD_PON` until BUSY releases after `CMD_POF`
+    powered: bool,
+    /// Set from `CMD_DRF` until its BUSY wait ends; still set after a
+    /// cancelled wait, while the waveform runs on
+    refreshing: bool,
+    /// Set by `CMD_DSLP`; only a hardware reset wakes the controllers
+    asleep: bool,
+    /// Set once the init sequence has been sent since the last reset
+    initialized: bool,
+    orientation: Orientation,
+    #[cfg(feature = "alloc")]
+    canvas: Canvas,
+    /// Packed frame buffers, reused from one refresh to the next
+    #[cfg(feature = "alloc")]
+    frame: PackedFrame,
 }
 
 impl InkyDisplay {
     /// Initialize the display
     ///
     /// This will set up GPIO pins, SPI communication, reset the display,
-    /// and send the initialization sequence.
+    /// and send the initialization sequence. Busy-wait timeouts only print a
+    /// warning; use [`new_strict`](Self::new_strict) to have them reported.
     pub fn new() -> Result<Self> {
-        let mut controller = DisplayController::new()?;
-        controller.reset()?;
+        Self::with_transport(DisplayController::new()?)
+    }
 
-        let mut display = Self {
-            controller,
-            buffer: PixelBuffer::new(WIDTH, HEIGHT),
-        };
+    /// Initialize the display in strict mode
+    ///
+    /// Like [`new`](Self::new), but fails with [`InkyError::NotConnected`] if
+    /// no panel is attached, and keeps reporting busy-wait timeouts as errors
+    /// afterwards. See [`set_strict`](InkyDisplay::set_strict).
+    ///
+    /// # Example
+    /// ```no_run
+    /// use inky_el133::{InkyDisplay, InkyError};
+    ///
+    /// match InkyDisplay::new_strict() {
+    ///     Ok(display) => assert!(display.is_strict()),
+    ///     Err(InkyError::NotConnected) => eprintln!("No panel on the HAT"),
+    ///     Err(err) => return Err(err),
+    /// }
+    /// # Ok::<(), inky_el133::InkyError>(())
+    /// ```
+    pub fn new_strict() -> Result<Self> {
+        Self::from_parts(DisplayController::new()?, Timing::default(), true)
+    }
+
+    /// Open the hardware without resetting or initializing the panel
+    ///
+    /// Whatever the panel is showing stays untouched. The first `show` resets
+    /// and initializes it, or call [`initialize`](Self::initialize) to choose
+    /// when that happens. If an earlier process left the panel initialized
+    /// and awake, [`assume_initialized`](Self::assume_initialized) skips the
+    /// reset altogether.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # #[cfg(feature = "std")] {
+    /// use inky_el133::InkyDisplay;
+    ///
+    /// let mut display = InkyDisplay::open()?;  // No reset, no init sequence
+    /// assert!(!display.is_initialized());
+    /// display.show()?;  // Resets and initializes first
+    /// # }
+    /// # Ok::<(), inky_el133::InkyError>(())
+    /// ```
+    pub fn open() -> Result<Self> {
+        Ok(Self::attach(DisplayController::new()?))
+    }
 
+    /// Configure pins, device paths and SPI settings before opening the display
+    ///
+    /// See [`InkyDisplayBuilder`] for the available settings and defaults.
+    #[cfg(feature = "std")]
+    pub fn builder() -> InkyDisplayBuilder {
+        InkyDisplayBuilder::new()
+    }
+}
+
+impl<T: Transport> InkyDisplay<T> {
+    /// Initialize the display over a custom transport
+    ///
+    /// Resets the display and sends the initialization sequence, exactly like
+    /// [`InkyDisplay::new`] does for the hardware controller.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use inky_el133::InkyDisplay;
+    /// use inky_el133::transport::RecordingTransport;
+    ///
+    /// let display = InkyDisplay::with_transport(RecordingTransport::new())?;
+    /// assert!(!display.transport().events().is_empty());
+    /// # }
+    /// # Ok::<(), inky_el133::InkyError>(())
+    /// ```
+    pub fn with_transport(transport: T) -> Result<Self> {
+        Self::with_timing(transport, Timing::default())
+    }
+
+    /// Initialize the display over a custom transport with a timing profile
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use inky_el133::{InkyDisplay, Timing};
+    /// use inky_el133::transport::{Event, RecordingTransport};
+    ///
+    /// let display = InkyDisplay::with_timing(RecordingTransport::new(), Timing::conservative())?;
+    /// let delays = display
+    ///     .transport()
+    ///     .events()
+    ///     .iter()
+    ///     .filter(|event| matches!(event, Event::Delay { ms: 300 }))
+    ///     .count();
+    /// assert_eq!(delays, 17); // one per init command
+    /// # }
+    /// # Ok::<(), inky_el133::InkyError>(())
+    /// ```
+    pub fn with_timing(transport: T, timing: Timing) -> Result<Self> {
+        Self::from_parts(transport, timing, false)
+    }
+
+    /// Wrap a custom transport without resetting or initializing the panel
+    ///
+    /// The transport-generic counterpart of [`InkyDisplay::open`].
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use inky_el133::InkyDisplay;
+    /// use inky_el133::transport::{Event, RecordingTransport};
+    ///
+    /// let mut display = InkyDisplay::attach(RecordingTransport::new());
+    /// assert!(display.transport().events().is_empty());
+    ///
+    /// display.show()?;
+    /// assert_eq!(display.transport().events().first(), Some(&Event::Reset));
+    /// assert!(display.is_initialized());
+    /// # }
+    /// # Ok::<(), inky_el133::InkyError>(())
+    /// ```
+    pub fn attach(transport: T) -> Self {
+        Self::attach_parts(transport, Timing::default(), false)
+    }
+
+    /// Reset and initialize the display with every setting spelled out
+    pub(crate) fn from_parts(transport: T, timing: Timing, strict: bool) -> Result<Self> {
+        let mut display = Self::attach_parts(transport, timing, strict);
         display.initialize()?;
         Ok(display)
     }
 
-    /// Send initialization command sequence to the display (inky_el133uf1.py:236-255)
-    fn initialize(&mut self) -> Result<()> {
-        self.controller.wait_busy(300)?;
+    /// Wrap a transport with every setting spelled out, leaving the panel alone
+    pub(crate) fn attach_parts(transport: T, timing: Timing, strict: bool) -> Self {
+        Self {
+            controller: Some(transport),
+            timing,
+            strict,
+            busy_times: BusyTimes::default(),
+            powered: false,
+            refreshing: false,
+            asleep: false,
+            initialized: false,
+            orientation: Orientation::LANDSCAPE,
+            #[cfg(feature = "alloc")]
+            canvas: Canvas::new(),
+            #[cfg(feature = "alloc")]
+            frame: PackedFrame::default(),
+        }
+    }
 
-        self.controller.send_command(
-            ChipSelect::CS0,
-            CMD_ANTM,
-            &[0xC0, 0x1C, 0x1C, 0xCC, 0xCC, 0xCC, 0x15, 0x15, 0x55],
-        )?;
+    /// Borrow the underlying transport
+    pub fn transport(&self) -> &T {
+        self.controller.as_ref().expect(TRANSPORT_TAKEN)
+    }
 
-        self.controller.send_command(
-            ChipSelect::Both,
-            CMD_CMD66,
-            &[0x49, 0x55, 0x13, 0x5D, 0x05, 0x10],
-        )?;
+    /// Mutably borrow the underlying transport
+    pub fn transport_mut(&mut self) -> &mut T {
+        self.controller.as_mut().expect(TRANSPORT_TAKEN)
+    }
 
-        self.controller
-            .send_command(ChipSelect::Both, CMD_PSR, &[0xDF, 0x69])?;
+    /// Consume the display and return the underlying transport
+    ///
+    /// Powers the panel off first if a refresh was interrupted.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use inky_el133::InkyDisplay;
+    /// use inky_el133::transport::{Event, RecordingTransport};
+    ///
+    /// let display = InkyDisplay::with_transport(RecordingTransport::new())?;
+    /// let transport = display.into_transport();
+    /// assert_eq!(transport.events().first(), Some(&Event::Reset));
+    /// # }
+    /// # Ok::<(), inky_el133::InkyError>(())
+    /// ```
+    pub fn into_transport(mut self) -> T {
+        // Best effort, like dropping the display
+        let _ = self.ensure_powered_off();
+        self.controller.take().expect(TRANSPORT_TAKEN)
+    }
 
-        self.controller
-            .send_command(ChipSelect::Both, CMD_PLL, &[0x08])?;
+    /// Timing profile in use
+    pub fn timing(&self) -> &Timing {
+        &self.timing
+    }
 
-        self.controller
-            .send_command(ChipSelect::Both, CMD_CDI, &[0xF7])?;
+    /// Change the timing profile for subsequent commands
+    pub fn set_timing(&mut self, timing: Timing) {
+        self.timing = timing;
+    }
 
-        self.controller
-            .send_command(ChipSelect::Both, CMD_TCON, &[0x03, 0x03])?;
+    /// Whether busy-wait timeouts are reported as errors
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
 
-        self.controller
-            .send_command(ChipSelect::Both, CMD_AGID, &[0x10])?;
+    /// Report busy-wait timeouts as errors instead of warnings
+    ///
+    /// In strict mode a timeout fails with [`InkyError::BusyTimeout`] naming
+    /// the phase, and initialization fails with [`InkyError::NotConnected`]
+    /// if BUSY reads high both before the reset and for the whole init wait,
+    /// as it does with no panel attached. Otherwise a warning is printed (with `std`) and the
+    /// refresh carries on, as the Python driver does.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
 
-        self.controller
-            .send_command(ChipSelect::Both, CMD_PWS, &[0x22])?;
+    /// BUSY durations measured during the most recent refresh
+    ///
+    /// # Example
+    /// ```no_run
+    /// # #[cfg(feature = "std")] {
+    /// # use inky_el133::InkyDisplay;
+    /// # let mut display = InkyDisplay::new()?;
+    /// display.show()?;
+    /// let busy = display.last_busy_times();
+    /// println!("PON {:?}, DRF {:?}, POF {:?}", busy.power_on, busy.refresh, busy.power_off);
+    /// # }
+    /// # Ok::<(), inky_el133::InkyError>(())
+    /// ```
+    pub fn last_busy_times(&self) -> BusyTimes {
+        self.busy_times
+    }
 
-        self.controller
-            .send_command(ChipSelect::Both, CMD_CCSET, &[0x01])?;
+    /// Apply the per-command delay, if any
+    fn command_delay(&mut self) {
+        let ms = self.timing.command_delay_ms;
+        if ms > 0 {
+            self.transport_mut().delay_ms(ms);
+        }
+    }
 
-        self.controller
-            .send_command(ChipSelect::Both, CMD_TRES, &[0x04, 0xB0, 0x03, 0x20])?;
+    /// Wait for BUSY like [`Transport::wait_busy`], but give up early once
+    /// `cancel` is triggered
+    fn wait_busy_cancellable(
+        &mut self,
+        timeout_ms: u64,
+        cancel: Option<&CancellationToken>,
+    ) -> Result<Option<Duration>> {
+        let Some(cancel) = cancel else {
+            return self.transport_mut().wait_busy(timeout_ms);
+        };
 
-        self.controller.send_command(
-            ChipSelect::CS0,
-            CMD_PWR,
-            &[0x0F, 0x00, 0x28, 0x2C, 0x28, 0x38],
-        )?;
+        let mut waited_ms = 0;
+        loop {
+            check_cancel(Some(cancel))?;
 
-        self.controller
-            .send_command(ChipSelect::CS0, CMD_EN_BUF, &[0x07])?;
+            let slice_ms = (timeout_ms - waited_ms).min(CANCEL_POLL_MS);
+            if let Some(elapsed) = self.transport_mut().wait_busy(slice_ms)? {
+                return Ok(Some(Duration::from_millis(waited_ms) + elapsed));
+            }
 
-        self.controller
-            .send_command(ChipSelect::CS0, CMD_BTST_P, &[0xD8, 0x18])?;
+            waited_ms += slice_ms;
+            if waited_ms >= timeout_ms {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Turn the result of a busy wait into a duration or a strict-mode error
+    fn busy_outcome(
+        &self,
+        phase: Phase,
+        timeout_ms: u64,
+        waited: Option<Duration>,
+    ) -> Result<Duration> {
+        match waited {
+            Some(elapsed) => Ok(elapsed),
+            None if self.strict => Err(InkyError::BusyTimeout(phase)),
+            None => {
+                #[cfg(feature = "std")]
+                eprintln!(
+                    "Warning: Busy wait timed out after {} ms during {}",
+                    timeout_ms, phase
+                );
+                Ok(Duration::from_millis(timeout_ms))
+            }
+        }
+    }
+
+    /// Send a command, honoring the timing profile
+    fn command(&mut self, cs: ChipSelect, cmd: u8, data: &[u8]) -> Result<()> {
+        self.command_delay();
+        self.transport_mut().send_command(cs, cmd, data)
+    }
+
+    /// Reset the panel and send the initialization sequence
+    ///
+    /// Displays created with [`open`](InkyDisplay::open) or
+    /// [`attach`](Self::attach) do this automatically before their first
+    /// refresh. Calling it again starts over from a hardware reset.
+    pub fn initialize(&mut self) -> Result<()> {
+        self.initialized = false;
+        let driven = !self.transport_mut().is_busy()?;
+        self.transport_mut().reset()?;
+        self.reset_done();
+
+        let timeout_ms = self.timing.init_timeout_ms;
+        let waited = self.transport_mut().wait_busy(timeout_ms)?;
+        self.init_outcome(driven, waited)?;
+        for (cs, cmd, data) in INIT_SEQUENCE {
+            self.command(cs, cmd, data)?;
+        }
+
+        self.initialized = true;
+        Ok(())
+    }
 
-        self.controller
-            .send_command(ChipSelect::CS0, CMD_BOOST_VDDP_EN, &[0x01])?;
+    /// Forget the controller state a hardware reset clears
+    fn reset_done(&mut self) {
+        self.powered = false;
+        self.refreshing = false;
+        self.asleep = false;
+    }
 
-        self.controller
-            .send_command(ChipSelect::CS0, CMD_BTST_N, &[0xD8, 0x18])?;
+    /// Treat the panel as initialized without resetting it or sending anything
+    ///
+    /// For a display from [`open`](InkyDisplay::open) or
+    /// [`attach`](Self::attach) whose panel another process, such as an
+    /// earlier run of the same service, already initialized and did not put
+    /// to sleep. The controllers keep their settings between refreshes, so
+    /// the next `show` can send its frame straight away. A panel that is in
+    /// deep sleep after all ignores that frame; call
+    /// [`initialize`](Self::initialize) when in doubt.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use inky_el133::InkyDisplay;
+    /// use inky_el133::transport::{Event, RecordingTransport};
+    ///
+    /// let mut display = InkyDisplay::attach(RecordingTransport::new());
+    /// display.assume_initialized();
+    ///
+    /// display.show()?;  // No reset, no init sequence
+    /// assert!(!display.transport().events().contains(&Event::Reset));
+    /// # }
+    /// # Ok::<(), inky_el133::InkyError>(())
+    /// ```
+    pub fn assume_initialized(&mut self) {
+        self.initialized = true;
+        self.asleep = false;
+    }
 
-        self.controller
-            .send_command(ChipSelect::CS0, CMD_BUCK_BOOST_VDDN, &[0x01])?;
+    /// Whether the init sequence has been sent since the last reset
+    pub fn is_initialized(&self) -> bool {
+        self.initialized
+    }
 
-        self.controller
-            .send_command(ChipSelect::CS0, CMD_TFT_VCOM_POWER, &[0x02])?;
+    /// Judge the BUSY wait after reset
+    ///
+    /// `driven` is whether BUSY read low (idle) just before the reset. With
+    /// nothing attached the line floats high on its pull-up, which reads as
+    /// busy both then and for the whole wait. A panel drives it low whenever
+    /// it is idle, so a wait that times out after the line was seen low is a
+    /// slow panel, not a missing one.
+    fn init_outcome(&mut self, driven: bool, waited: Option<Duration>) -> Result<()> {
+        if waited.is_some() || driven {
+            return self
+                .busy_outcome(Phase::Init, self.timing.init_timeout_ms, waited)
+                .map(drop);
+        }
+        if self.strict {
+            return Err(InkyError::NotConnected);
+        }
 
+        #[cfg(feature = "std")]
+        eprintln!("Warning: BUSY never went low around reset; is the display connected?");
         Ok(())
     }
 
-    /// Set a single pixel
+    /// Mounting of the panel
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Change how logical coordinates map onto the glass
+    ///
+    /// [`width`](Self::width) and [`height`](Self::height) follow the new
+    /// orientation. If they change, the display's canvas is replaced with a
+    /// blank white one of the new size.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use inky_el133::{InkyDisplay, Orientation, Rotation, colors};
+    /// use inky_el133::transport::RecordingTransport;
+    ///
+    /// let mut display = InkyDisplay::attach(RecordingTransport::new());
+    /// display.set_orientation(Orientation::new(Rotation::Deg90));
+    /// assert_eq!((display.width(), display.height()), (1200, 1600));
+    ///
+    /// display.set_pixel(1199, 1599, colors::RED)?;
+    /// display.show()?;
+    /// # }
+    /// # Ok::<(), inky_el133::InkyError>(())
+    /// ```
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+
+        #[cfg(feature = "alloc")]
+        if !self.canvas.fits(orientation) {
+            self.canvas = Canvas::with_orientation(orientation);
+        }
+    }
+
+    /// Logical width in pixels (1600, or 1200 in portrait)
+    pub fn width(&self) -> usize {
+        self.orientation.width()
+    }
+
+    /// Logical height in pixels (1200, or 1600 in portrait)
+    pub fn height(&self) -> usize {
+        self.orientation.height()
+    }
+
+    /// Set a single pixel on the display's canvas
     ///
     /// # Arguments
-    /// * `x` - X coordinate (0-1599)
-    /// * `y` - Y coordinate (0-1199)
-    /// * `color` - Color index (0, 1, 2, 3, 5, or 6)
+    /// * `x` - X coordinate (0 to [`width`](Self::width) - 1)
+    /// * `y` - Y coordinate (0 to [`height`](Self::height) - 1)
+    /// * `color` - One of the six panel colors
     ///
     /// # Example
     /// ```no_run
@@ -141,57 +651,439 @@ impl InkyDisplay {
     /// display.set_pixel(100, 200, colors::RED)?;
     /// # Ok::<(), inky_el133::InkyError>(())
     /// ```
-    pub fn set_pixel(&mut self, x: usize, y: usize, color: u8) -> Result<()> {
-        self.buffer.set_pixel(x, y, color)
+    #[cfg(feature = "alloc")]
+    pub fn set_pixel(&mut self, x: usize, y: usize, color: Color) -> Result<()> {
+        self.canvas.set_pixel(x, y, color)
     }
 
-    /// Fill the entire buffer with a single color
+    /// Fill the display's canvas with a single color
     ///
     /// # Example
     /// ```no_run
     /// # use inky_el133::{InkyDisplay, colors};
     /// # let mut display = InkyDisplay::new()?;
-    /// display.fill(colors::WHITE)?;
+    /// display.fill(colors::WHITE);
     /// # Ok::<(), inky_el133::InkyError>(())
     /// ```
-    pub fn fill(&mut self, color: u8) -> Result<()> {
-        self.buffer.fill(color)
+    #[cfg(feature = "alloc")]
+    pub fn fill(&mut self, color: Color) {
+        self.canvas.fill(color)
     }
 
-    /// Update the display with the current buffer contents
+    /// Borrow the canvas that [`show`](Self::show) sends
+    #[cfg(feature = "alloc")]
+    pub fn canvas(&self) -> &Canvas {
+        &self.canvas
+    }
+
+    /// Mutably borrow the canvas that [`show`](Self::show) sends
+    #[cfg(feature = "alloc")]
+    pub fn canvas_mut(&mut self) -> &mut Canvas {
+        &mut self.canvas
+    }
+
+    /// Update the display with the contents of its canvas
     ///
     /// This operation takes approximately 32 seconds due to hardware limitations.
-    /// The display will rotate the buffer, split it between the two controllers,
+    /// The display will rotate the canvas, split it between the two controllers,
     /// and refresh the screen.
     ///
     /// # Example
     /// ```no_run
     /// # use inky_el133::{InkyDisplay, colors};
     /// # let mut display = InkyDisplay::new()?;
-    /// display.fill(colors::BLACK)?;
+    /// display.fill(colors::BLACK);
     /// display.show()?;  // Takes ~32 seconds
     /// # Ok::<(), inky_el133::InkyError>(())
     /// ```
+    #[cfg(feature = "alloc")]
     pub fn show(&mut self) -> Result<()> {
-        let (buf_a, buf_b) = self.buffer.rotate_and_split();
+        self.show_frame(None, None)
+    }
 
-        self.controller
-            .send_command(ChipSelect::CS0, CMD_DTM, &buf_a)?;
-        self.controller
-            .send_command(ChipSelect::CS1, CMD_DTM, &buf_b)?;
+    /// Update the display with a canvas rendered elsewhere
+    ///
+    /// Sends `canvas` instead of the display's own canvas, which is left
+    /// unchanged. The canvas must have the display's logical size, or
+    /// [`InkyError::InvalidBufferSize`] is returned before the panel is
+    /// reset, woken or sent anything.
+    ///
+    /// # Example
+    /// ```
+    /// use inky_el133::{Canvas, InkyDisplay, colors};
+    /// use inky_el133::transport::RecordingTransport;
+    ///
+    /// let mut canvas = Canvas::new();
+    /// canvas.fill(colors::BLUE);
+    ///
+    /// let mut display = InkyDisplay::with_transport(RecordingTransport::new())?;
+    /// display.show_canvas(&canvas)?;
+    /// # Ok::<(), inky_el133::InkyError>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn show_canvas(&mut self, canvas: &Canvas) -> Result<()> {
+        self.show_frame(Some(canvas), None)
+    }
 
-        self.controller
-            .send_command(ChipSelect::Both, CMD_PON, &[])?;
-        self.controller.wait_busy(200)?;
+    /// Update the display, stopping early if `cancel` is triggered
+    ///
+    /// Like [`show`](Self::show), but checks `cancel` before sending any data,
+    /// before starting the refresh and while waiting for it to finish. A
+    /// cancelled refresh returns [`InkyError::Cancelled`] after the panel has
+    /// been powered off.
+    ///
+    /// Cancelling before `CMD_DRF` returns promptly. A refresh that has
+    /// started cannot be stopped: the controllers only accept `CMD_POF` once
+    /// it completes, so cancelling then still returns only after the full
+    /// refresh (about 32 seconds, up to the refresh timeout), with the new
+    /// frame on the glass.
+    #[cfg(feature = "alloc")]
+    pub fn show_cancellable(&mut self, cancel: &CancellationToken) -> Result<()> {
+        check_cancel(Some(cancel))?;
 
-        self.controller
-            .send_command(ChipSelect::Both, CMD_DRF, &[0x00])?;
-        self.controller.wait_busy(32000)?;
+        self.show_frame(None, Some(cancel))
+    }
 
-        self.controller
-            .send_command(ChipSelect::Both, CMD_POF, &[0x00])?;
-        self.controller.wait_busy(200)?;
+    /// Update the display with a frame stored at 4 bits per pixel
+    ///
+    /// Like [`show_canvas`](Self::show_canvas), but for a [`PackedCanvas`].
+    ///
+    /// # Example
+    /// ```
+    /// use inky_el133::{Canvas, InkyDisplay, PackedCanvas, colors};
+    /// use inky_el133::transport::{ChipSelect, RecordingTransport};
+    ///
+    /// let mut canvas = Canvas::new();
+    /// canvas.fill(colors::GREEN);
+    ///
+    /// let mut display = InkyDisplay::with_transport(RecordingTransport::new())?;
+    /// display.show_canvas(&canvas)?;
+    /// let unpacked = display.transport().command_data(ChipSelect::CS0, 0x10).unwrap().to_vec();
+    ///
+    /// display.show_packed_canvas(&PackedCanvas::from(&canvas))?;
+    /// let packed = display.transport().command_data(ChipSelect::CS0, 0x10).unwrap();
+    /// assert_eq!(packed, unpacked);
+    /// # Ok::<(), inky_el133::InkyError>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn show_packed_canvas(&mut self, canvas: &PackedCanvas) -> Result<()> {
+        self.show_frame(Some(canvas), None)
+    }
+
+    /// Update the display with a frame already in native panel order
+    ///
+    /// The two halves of `canvas` are sent to the controllers unchanged, with
+    /// no rotation or packing, so this is the fastest way to show a frame.
+    /// The display's [`Orientation`] does not apply.
+    ///
+    /// # Example
+    /// ```
+    /// use inky_el133::{Canvas, Color, InkyDisplay, NativeCanvas};
+    /// use inky_el133::transport::{ChipSelect, RecordingTransport};
+    ///
+    /// // Native (0, 0) is the top right corner of the landscape frame
+    /// let mut canvas = Canvas::new();
+    /// canvas.set_pixel(1599, 0, Color::Red)?;
+    /// let mut native = NativeCanvas::new();
+    /// native.set_pixel(0, 0, Color::Red)?;
+    ///
+    /// let mut display = InkyDisplay::with_transport(RecordingTransport::new())?;
+    /// display.show_canvas(&canvas)?;
+    /// let rotated = display.transport().command_data(ChipSelect::CS0, 0x10).unwrap().to_vec();
+    ///
+    /// display.show_native(&native)?;
+    /// let sent = display.transport().command_data(ChipSelect::CS0, 0x10).unwrap();
+    /// assert_eq!(sent, rotated);
+    /// # Ok::<(), inky_el133::InkyError>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn show_native(&mut self, canvas: &NativeCanvas) -> Result<()> {
+        self.ensure_ready()?;
+
+        let (cs0, cs1) = canvas.halves();
+        self.send_halves(cs0, cs1)?;
+        self.refresh(None)
+    }
+
+    /// Pack `canvas`, or the display's own canvas, into the frame buffers
+    /// for the current orientation
+    ///
+    /// Fails without touching the panel if the canvas does not have the
+    /// display's logical size. The buffers are moved out of the display; put
+    /// them back in `self.frame` once they have been sent.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn take_frame(&mut self, canvas: Option<&dyn FrameSource>) -> Result<PackedFrame> {
+        let canvas = canvas.unwrap_or(&self.canvas);
+        if !canvas.fits(self.orientation) {
+            return Err(InkyError::InvalidBufferSize);
+        }
+
+        let mut frame = core::mem::take(&mut self.frame);
+        canvas.rotate_and_split_into(self.orientation, &mut frame);
+        Ok(frame)
+    }
+
+    /// Pack a canvas, send both halves and refresh
+    #[cfg(feature = "alloc")]
+    fn show_frame(
+        &mut self,
+        canvas: Option<&dyn FrameSource>,
+        cancel: Option<&CancellationToken>,
+    ) -> Result<()> {
+        let frame = self.take_frame(canvas)?;
+        self.send_frame(frame, cancel)
+    }
+
+    /// Wake or initialize the panel if needed, send both halves of a packed
+    /// frame, keep its buffers and refresh
+    #[cfg(feature = "alloc")]
+    fn send_frame(&mut self, frame: PackedFrame, cancel: Option<&CancellationToken>) -> Result<()> {
+        let result = self
+            .ensure_ready()
+            .and_then(|()| self.send_halves(&frame.cs0, &frame.cs1));
+        self.frame = frame;
+        result?;
+
+        self.refresh(cancel)
+    }
+
+    /// Send the packed CS0 and CS1 frame data
+    #[cfg(feature = "alloc")]
+    fn send_halves(&mut self, cs0: &[u8], cs1: &[u8]) -> Result<()> {
+        self.command(ChipSelect::CS0, CMD_DTM, cs0)?;
+        self.command(ChipSelect::CS1, CMD_DTM, cs1)
+    }
+
+    /// Update the display with pixels produced on the fly
+    ///
+    /// `pixel(x, y)` is called for every logical coordinate (x below
+    /// [`width`](Self::width), y below [`height`](Self::height)) and returns
+    /// its color. The frame is packed and sent
+    /// row by row without allocating, producing exactly the bytes
+    /// [`show`](Self::show) would send for the same image. This ignores the
+    /// display's canvas.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use inky_el133::{InkyDisplay, colors};
+    /// use inky_el133::transport::RecordingTransport;
+    ///
+    /// let checker = |x: usize, y: usize| {
+    ///     if (x / 100 + y / 100) % 2 == 0 { colors::BLACK } else { colors::YELLOW }
+    /// };
+    ///
+    /// let mut display = InkyDisplay::with_transport(RecordingTransport::new())?;
+    /// # display.transport_mut().clear();
+    /// display.show_with(checker)?;
+    /// # let streamed = display.transport().clone();
+    /// # display.transport_mut().clear();
+    /// # for y in 0..1200 {
+    /// #     for x in 0..1600 {
+    /// #         display.set_pixel(x, y, checker(x, y))?;
+    /// #     }
+    /// # }
+    /// # display.show()?;
+    /// # assert_eq!(streamed.events(), display.transport().events());
+    /// # }
+    /// # Ok::<(), inky_el133::InkyError>(())
+    /// ```
+    pub fn show_with<F>(&mut self, mut pixel: F) -> Result<()>
+    where
+        F: FnMut(usize, usize) -> Color,
+    {
+        self.stream_frame(|x, y| Ok(pixel(x, y)))
+    }
+
+    /// Update the display from a caller-owned frame
+    ///
+    /// `pixels` holds [`width`](Self::width) × [`height`](Self::height) color
+    /// indices in row-major order. Like
+    /// [`show_with`](Self::show_with), the frame is streamed without
+    /// allocating. An index that is not a [`Color`] aborts the transfer with
+    /// [`InkyError::InvalidColor`] before the refresh starts.
+    pub fn show_from_slice(&mut self, pixels: &[u8]) -> Result<()> {
+        if pixels.len() != WIDTH * HEIGHT {
+            return Err(InkyError::InvalidBufferSize);
+        }
+
+        let width = self.width();
+        self.stream_frame(|x, y| Color::try_from(pixels[y * width + x]))
+    }
+
+    /// Stream both halves from a fallible pixel source and refresh
+    fn stream_frame<F>(&mut self, mut pixel: F) -> Result<()>
+    where
+        F: FnMut(usize, usize) -> Result<Color>,
+    {
+        self.ensure_ready()?;
+
+        let orientation = self.orientation;
+        self.command_delay();
+        stream::send_half(
+            self.transport_mut(),
+            ChipSelect::CS0,
+            orientation,
+            &mut pixel,
+        )?;
+        self.command_delay();
+        stream::send_half(
+            self.transport_mut(),
+            ChipSelect::CS1,
+            orientation,
+            &mut pixel,
+        )?;
+
+        self.refresh(None)
+    }
+
+    /// Power on, refresh from controller memory and power off again
+    ///
+    /// The panel is powered off even if a step fails or is cancelled. If
+    /// powering off fails as well, that error is returned instead, since the
+    /// panel is then in an unknown state.
+    fn refresh(&mut self, cancel: Option<&CancellationToken>) -> Result<()> {
+        let result = self.run_refresh(cancel);
+        if result.is_err() {
+            self.ensure_powered_off()?;
+        }
+        result
+    }
+
+    fn run_refresh(&mut self, cancel: Option<&CancellationToken>) -> Result<()> {
+        for step in REFRESH_STEPS {
+            // Only the refresh itself is worth cancelling
+            let cancel = cancel.filter(|_| step.phase == Phase::Refresh);
+            check_cancel(cancel)?;
+            self.run_step(step, cancel)?;
+        }
+        Ok(())
+    }
+
+    /// Send `CMD_POF` and wait for the panel to power down
+    fn power_off(&mut self) -> Result<()> {
+        self.run_step(POWER_OFF, None)
+    }
+
+    /// Send one refresh command and wait for BUSY to release
+    fn run_step(&mut self, step: Step, cancel: Option<&CancellationToken>) -> Result<()> {
+        self.start_step(step);
+        self.command(ChipSelect::Both, step.cmd, step.data)?;
+        let waited = self.wait_busy_cancellable(self.timing.timeout_ms(step.phase), cancel)?;
+        self.finish_step(step, waited)
+    }
+
+    /// Bookkeeping before a refresh step's command is sent
+    fn start_step(&mut self, step: Step) {
+        match step.phase {
+            Phase::PowerOn => {
+                self.busy_times = BusyTimes::default();
+                self.powered = true;
+            }
+            Phase::Refresh => self.refreshing = true,
+            Phase::Init | Phase::PowerOff => {}
+        }
+    }
+
+    /// Record how long a refresh step held BUSY, or fail if it timed out in
+    /// strict mode
+    fn finish_step(&mut self, step: Step, waited: Option<Duration>) -> Result<()> {
+        match step.phase {
+            Phase::Refresh => self.refreshing = false,
+            Phase::PowerOff if waited.is_some() => self.powered = false,
+            _ => {}
+        }
+
+        let timeout_ms = self.timing.timeout_ms(step.phase);
+        let elapsed = self.busy_outcome(step.phase, timeout_ms, waited)?;
+        match step.phase {
+            Phase::PowerOn => self.busy_times.power_on = elapsed,
+            Phase::Refresh => self.busy_times.refresh = elapsed,
+            Phase::PowerOff => self.busy_times.power_off = elapsed,
+            Phase::Init => {}
+        }
+        Ok(())
+    }
+
+    /// Put both controllers into deep sleep
+    ///
+    /// Deep sleep keeps the image on the glass and draws far less current
+    /// than standby after `CMD_POF`. The controllers ignore everything except
+    /// a hardware reset until [`wake`](Self::wake) is called; every `show`
+    /// method wakes the panel first, so calling `wake` is only needed to
+    /// control when the reset and init sequence happen.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use inky_el133::InkyDisplay;
+    /// use inky_el133::transport::{Event, RecordingTransport};
+    ///
+    /// let mut display = InkyDisplay::with_transport(RecordingTransport::new())?;
+    /// display.sleep()?;
+    /// assert!(display.is_asleep());
+    ///
+    /// display.transport_mut().clear();
+    /// display.show()?;  // Resets and re-initializes the panel first
+    /// assert_eq!(display.transport().events().first(), Some(&Event::Reset));
+    /// assert!(!display.is_asleep());
+    /// # }
+    /// # Ok::<(), inky_el133::InkyError>(())
+    /// ```
+    pub fn sleep(&mut self) -> Result<()> {
+        if self.asleep {
+            return Ok(());
+        }
+
+        self.ensure_powered_off()?;
+        self.command(ChipSelect::Both, CMD_DSLP, &[DSLP_CHECK])?;
+        self.asleep = true;
+        Ok(())
+    }
+
+    /// Bring the controllers out of deep sleep
+    ///
+    /// Pulses the reset line and re-sends the init sequence. Does nothing if
+    /// the panel is not asleep.
+    pub fn wake(&mut self) -> Result<()> {
+        if self.asleep {
+            self.initialize()?;
+        }
+        Ok(())
+    }
+
+    /// Whether [`sleep`](Self::sleep) has been called since the last wake
+    pub fn is_asleep(&self) -> bool {
+        self.asleep
+    }
+
+    /// Whether the panel must be reset and initialized before a refresh
+    pub(crate) fn needs_init(&self) -> bool {
+        self.asleep || !self.initialized
+    }
+
+    /// Reset and initialize the panel if it is asleep or was never initialized
+    pub(crate) fn ensure_ready(&mut self) -> Result<()> {
+        if self.needs_init() {
+            self.initialize()?;
+        }
+        Ok(())
+    }
 
+    /// Power off after an interrupted refresh
+    ///
+    /// A cancelled refresh keeps running its waveform, and the controllers
+    /// only take `CMD_POF` once it has finished, so BUSY is waited out first.
+    /// The panel counts as powered until BUSY releases after `CMD_POF`.
+    fn ensure_powered_off(&mut self) -> Result<()> {
+        if self.refreshing {
+            let timeout_ms = self.timing.refresh_timeout_ms;
+            let waited = self.transport_mut().wait_busy(timeout_ms)?;
+            self.finish_step(REFRESH, waited)?;
+        }
+        if self.powered {
+            self.power_off()?;
+        }
         Ok(())
     }
 
@@ -206,8 +1098,350 @@ impl InkyDisplay {
     /// display.clear()?;  // Takes ~32 seconds
     /// # Ok::<(), inky_el133::InkyError>(())
     /// ```
+    #[cfg(feature = "alloc")]
     pub fn clear(&mut self) -> Result<()> {
-        self.fill(colors::WHITE)?;
+        self.fill(colors::WHITE);
         self.show()
     }
 }
+
+impl<T: Transport> Drop for InkyDisplay<T> {
+    /// Power the panel off if a refresh was interrupted
+    fn drop(&mut self) {
+        // After into_transport there is nothing left to power off
+        if self.controller.is_some() {
+            // Nothing can be reported from here
+            let _ = self.ensure_powered_off();
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::transport::{Event, RecordingTransport};
+
+    /// Phases in the order `show` waits on them after `attach`
+    const PHASES: [Phase; 4] = [Phase::Init, Phase::PowerOn, Phase::Refresh, Phase::PowerOff];
+
+    /// A display whose wait during `timed_out` times out
+    fn display_timing_out(timed_out: Phase, strict: bool) -> InkyDisplay<RecordingTransport> {
+        let mut transport = RecordingTransport::new();
+        for phase in PHASES {
+            transport.push_wait((phase != timed_out).then_some(Duration::from_millis(5)));
+        }
+        let mut display = InkyDisplay::attach(transport);
+        display.set_strict(strict);
+        display
+    }
+
+    fn commands(display: &InkyDisplay<RecordingTransport>) -> Vec<u8> {
+        display
+            .transport()
+            .commands()
+            .map(|(_, cmd, _)| cmd)
+            .collect()
+    }
+
+    #[test]
+    fn strict_timeout_names_the_phase() {
+        for phase in PHASES {
+            let mut display = display_timing_out(phase, true);
+            match display.show() {
+                Err(InkyError::BusyTimeout(failed)) => assert_eq!(failed, phase),
+                other => panic!("{phase}: expected BusyTimeout, got {other:?}"),
+            }
+
+            // Whatever failed, the panel is not left powered
+            let commands = commands(&display);
+            if commands.contains(&CMD_PON) {
+                assert_eq!(commands.last(), Some(&CMD_POF), "{phase}");
+            }
+        }
+    }
+
+    #[test]
+    fn lenient_timeout_carries_on() {
+        for phase in PHASES {
+            let mut display = display_timing_out(phase, false);
+            display.show().unwrap();
+            assert_eq!(commands(&display).last(), Some(&CMD_POF), "{phase}");
+
+            // A timed out phase counts as having taken its whole timeout
+            let (timing, busy) = (display.timing, display.last_busy_times());
+            let (measured, timeout_ms) = match phase {
+                Phase::Init => continue,
+                Phase::PowerOn => (busy.power_on, timing.power_on_timeout_ms),
+                Phase::Refresh => (busy.refresh, timing.refresh_timeout_ms),
+                Phase::PowerOff => (busy.power_off, timing.power_off_timeout_ms),
+            };
+            assert_eq!(measured, Duration::from_millis(timeout_ms), "{phase}");
+        }
+    }
+
+    /// A single BUSY line that both `is_busy` and `wait_busy` read, as on
+    /// real hardware; high reads as busy
+    struct BusyLine {
+        high: bool,
+        /// Level the line takes on reset
+        high_after_reset: bool,
+        /// Whether a controller ever pulls the line low again
+        releases: bool,
+    }
+
+    impl Transport for BusyLine {
+        fn reset(&mut self) -> Result<()> {
+            self.high = self.high_after_reset;
+            Ok(())
+        }
+
+        fn begin_command(&mut self, _cs: ChipSelect, _cmd: u8) -> Result<()> {
+            Ok(())
+        }
+
+        fn write_data(&mut self, _data: &[u8]) -> Result<()> {
+            Ok(())
+        }
+
+        fn end_command(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        fn wait_busy(&mut self, _timeout_ms: u64) -> Result<Option<Duration>> {
+            if !self.high {
+                return Ok(Some(Duration::ZERO));
+            }
+            if !self.releases {
+                return Ok(None);
+            }
+            self.high = false;
+            Ok(Some(Duration::from_millis(5)))
+        }
+
+        fn is_busy(&mut self) -> Result<bool> {
+            Ok(self.high)
+        }
+
+        fn delay_ms(&mut self, _ms: u64) {}
+    }
+
+    #[test]
+    fn missing_panel_is_not_connected_only_when_strict() {
+        // Pulled up and never driven
+        let mut display = InkyDisplay::attach(BusyLine {
+            high: true,
+            high_after_reset: true,
+            releases: false,
+        });
+
+        display.set_strict(true);
+        assert!(matches!(display.initialize(), Err(InkyError::NotConnected)));
+        assert!(!display.is_initialized());
+
+        display.set_strict(false);
+        display.initialize().unwrap();
+        assert!(display.is_initialized());
+    }
+
+    #[test]
+    fn panel_busy_after_reset_is_connected() {
+        let mut display = InkyDisplay::attach(BusyLine {
+            high: false,
+            high_after_reset: true,
+            releases: true,
+        });
+        display.set_strict(true);
+        display.initialize().unwrap();
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn async_timeout_powers_off_like_sync() {
+        let mut display = InkyDisplay::with_transport(RecordingTransport::new()).unwrap();
+        display.set_strict(true);
+        display.transport_mut().set_busy(true);
+
+        // Power on times out, and so does the power off after it
+        let result = display.show_async(|_| {}).await;
+        assert!(matches!(
+            result,
+            Err(InkyError::BusyTimeout(Phase::PowerOff))
+        ));
+        assert_eq!(commands(&display).last(), Some(&CMD_POF));
+        assert!(display.powered);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test(start_paused = true)]
+    async fn async_show_initializes_without_blocking() {
+        let mut display = InkyDisplay::attach(RecordingTransport::new());
+        display.set_timing(Timing::conservative());
+
+        display.show_async(|_| {}).await.unwrap();
+        assert!(display.is_initialized());
+
+        // Sync code would time the reset and command delays with Delay events
+        let events = display.transport().events();
+        assert_eq!(events.first(), Some(&Event::Reset));
+        assert!(!events.iter().any(|e| matches!(e, Event::Delay { .. })));
+        assert_eq!(
+            commands(&display)[..INIT_SEQUENCE.len()],
+            INIT_SEQUENCE.map(|(_, cmd, _)| cmd)
+        );
+    }
+
+    /// Records like [`RecordingTransport`], cancelling `token` once `CMD_DRF`
+    /// has been sent
+    struct CancelOnRefresh<'a> {
+        inner: RecordingTransport,
+        token: &'a CancellationToken,
+    }
+
+    impl Transport for CancelOnRefresh<'_> {
+        fn reset(&mut self) -> Result<()> {
+            self.inner.reset()
+        }
+
+        fn begin_command(&mut self, cs: ChipSelect, cmd: u8) -> Result<()> {
+            if cmd == CMD_DRF {
+                self.token.cancel();
+            }
+            self.inner.begin_command(cs, cmd)
+        }
+
+        fn write_data(&mut self, data: &[u8]) -> Result<()> {
+            self.inner.write_data(data)
+        }
+
+        fn end_command(&mut self) -> Result<()> {
+            self.inner.end_command()
+        }
+
+        fn wait_busy(&mut self, timeout_ms: u64) -> Result<Option<Duration>> {
+            self.inner.wait_busy(timeout_ms)
+        }
+
+        fn is_busy(&mut self) -> Result<bool> {
+            self.inner.is_busy()
+        }
+
+        fn delay_ms(&mut self, ms: u64) {
+            self.inner.delay_ms(ms)
+        }
+    }
+
+    /// Operations from `CMD_DRF` onwards
+    fn after_refresh(events: &[Event]) -> &[Event] {
+        let drf = events
+            .iter()
+            .position(|event| matches!(event, Event::Command { cmd: CMD_DRF, .. }))
+            .expect("DRF was sent");
+        &events[drf + 1..]
+    }
+
+    #[test]
+    fn cancel_during_refresh_waits_before_power_off() {
+        let token = CancellationToken::new();
+        let transport = CancelOnRefresh {
+            inner: RecordingTransport::new(),
+            token: &token,
+        };
+        let mut display = InkyDisplay::with_transport(transport).unwrap();
+
+        let result = display.show_cancellable(&token);
+        assert!(matches!(result, Err(InkyError::Cancelled)));
+        assert!(!display.powered && !display.refreshing);
+
+        let timing = display.timing;
+        let off = Event::Command {
+            cs: ChipSelect::Both,
+            cmd: CMD_POF,
+            data: [0x00].into(),
+        };
+        assert_eq!(
+            after_refresh(display.transport().inner.events()),
+            [
+                Event::WaitBusy {
+                    timeout_ms: timing.refresh_timeout_ms
+                },
+                off,
+                Event::WaitBusy {
+                    timeout_ms: timing.power_off_timeout_ms
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn failed_power_off_is_reported_and_retried() {
+        let token = CancellationToken::new();
+        let mut inner = RecordingTransport::new();
+        for waited in [Some(Duration::ZERO); 3] {
+            inner.push_wait(waited); // init, power on, rest of the refresh
+        }
+        inner.push_wait(None); // power off
+        let transport = CancelOnRefresh {
+            inner,
+            token: &token,
+        };
+        let mut display = InkyDisplay::attach(transport);
+        display.set_strict(true);
+
+        let result = display.show_cancellable(&token);
+        assert!(matches!(
+            result,
+            Err(InkyError::BusyTimeout(Phase::PowerOff))
+        ));
+        assert!(display.powered);
+
+        // Going to sleep powers off first, this time successfully
+        display.sleep().unwrap();
+        assert!(!display.powered);
+        let pofs = display
+            .transport()
+            .inner
+            .commands()
+            .filter(|&(_, cmd, _)| cmd == CMD_POF)
+            .count();
+        assert_eq!(pofs, 2);
+    }
+
+    #[test]
+    fn wrong_size_canvas_leaves_panel_alone() {
+        let portrait = Orientation::new(Rotation::Deg90);
+        let mut display = InkyDisplay::with_transport(RecordingTransport::new()).unwrap();
+        display.sleep().unwrap();
+        display.transport_mut().clear();
+
+        let canvas = Canvas::with_orientation(portrait);
+        assert!(matches!(
+            display.show_canvas(&canvas),
+            Err(InkyError::InvalidBufferSize)
+        ));
+        let packed = PackedCanvas::with_orientation(portrait);
+        assert!(matches!(
+            display.show_packed_canvas(&packed),
+            Err(InkyError::InvalidBufferSize)
+        ));
+
+        assert!(display.transport().events().is_empty());
+        assert!(display.is_asleep());
+    }
+
+    #[test]
+    fn slow_panel_is_a_timeout_not_a_missing_panel() {
+        // Idle before the reset, then still busy when the init wait gives up
+        let mut display = InkyDisplay::attach(BusyLine {
+            high: false,
+            high_after_reset: true,
+            releases: false,
+        });
+        display.set_strict(true);
+        assert!(matches!(
+            display.initialize(),
+            Err(InkyError::BusyTimeout(Phase::Init))
+        ));
+    }
+}
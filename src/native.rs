@@ -0,0 +1,126 @@
+use alloc::vec;
+
+use crate::Color;
+use crate::canvas::PackedFrame;
+use crate::constants::*;
+use crate::error::{InkyError, Result};
+
+/// Offscreen frame laid out exactly as the controllers receive it
+///
+/// The panel's native frame is 1200 pixels wide and 1600 tall, the landscape
+/// frame turned a quarter turn counter-clockwise, and is split at column 600
+/// between the two controllers. A `NativeCanvas` stores the two halves packed
+/// at 4 bits per pixel, so [`InkyDisplay::show_native`] sends them as they
+/// are with no rotation, splitting or packing. It suits renderers that can
+/// draw in panel order directly.
+///
+/// Coordinates are native: `x` in 0..1200, `y` in 0..1600, origin top left.
+/// Native `(x, y)` shows landscape pixel `(1599 - y, x)`, whatever the
+/// display's [`Orientation`](crate::Orientation).
+///
+/// [`InkyDisplay::show_native`]: crate::InkyDisplay::show_native
+///
+/// # Example
+/// ```
+/// use inky_el133::{Color, NativeCanvas};
+///
+/// let mut frame = NativeCanvas::new();
+/// frame.set_pixel(1, 0, Color::Red)?;
+/// frame.set_pixel(600, 0, Color::Blue)?;
+///
+/// let (cs0, cs1) = frame.halves();
+/// assert_eq!((cs0[0], cs1[0]), (0x13, 0x51));
+///
+/// // Render straight into the packed bytes, two pixels per byte
+/// let (cs0, _) = frame.halves_mut();
+/// cs0[..300].fill(0x22);
+/// assert_eq!(frame.get_pixel(599, 0), Some(Color::Yellow));
+/// # Ok::<(), inky_el133::InkyError>(())
+/// ```
+#[derive(Debug)]
+pub struct NativeCanvas {
+    frame: PackedFrame,
+}
+
+impl Default for NativeCanvas {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NativeCanvas {
+    /// Native width in pixels
+    pub const WIDTH: usize = HEIGHT;
+    /// Native height in pixels
+    pub const HEIGHT: usize = WIDTH;
+
+    /// Create a native frame filled with white
+    pub fn new() -> Self {
+        let white = Color::White.pair();
+        Self {
+            frame: PackedFrame {
+                cs0: vec![white; HALF_BYTES],
+                cs1: vec![white; HALF_BYTES],
+            },
+        }
+    }
+
+    /// Color at native `(x, y)`, or `None` if out of bounds
+    pub fn get_pixel(&self, x: usize, y: usize) -> Option<Color> {
+        let (half, index, shift) = self.locate(x, y)?;
+        let byte = if half {
+            self.frame.cs1[index]
+        } else {
+            self.frame.cs0[index]
+        };
+        Some(Color::from_nibble(byte >> shift))
+    }
+
+    /// Set a single pixel at native `(x, y)`
+    pub fn set_pixel(&mut self, x: usize, y: usize, color: Color) -> Result<()> {
+        let (half, index, shift) = self.locate(x, y).ok_or(InkyError::OutOfBounds(x, y))?;
+        let byte = if half {
+            &mut self.frame.cs1[index]
+        } else {
+            &mut self.frame.cs0[index]
+        };
+        *byte = (*byte & !(0x0F << shift)) | (color.index() << shift);
+        Ok(())
+    }
+
+    /// Fill the entire frame with a single color
+    pub fn fill(&mut self, color: Color) {
+        self.frame.cs0.fill(color.pair());
+        self.frame.cs1.fill(color.pair());
+    }
+
+    /// Packed data for CS0 (columns 0..600) and CS1 (columns 600..1200)
+    ///
+    /// Each half holds 1600 rows of 300 bytes, two pixels per byte with the
+    /// left pixel in the high nibble: exactly the `CMD_DTM` payload.
+    pub fn halves(&self) -> (&[u8], &[u8]) {
+        (&self.frame.cs0, &self.frame.cs1)
+    }
+
+    /// Packed data for CS0 and CS1, mutably
+    ///
+    /// Only color indices 0–3, 5 and 6 are valid nibbles; anything else is
+    /// sent to the panel as is.
+    pub fn halves_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        (&mut self.frame.cs0, &mut self.frame.cs1)
+    }
+
+    /// Which half, byte and nibble shift hold native `(x, y)`
+    fn locate(&self, x: usize, y: usize) -> Option<(bool, usize, u8)> {
+        if x >= Self::WIDTH || y >= Self::HEIGHT {
+            return None;
+        }
+        let (half, col) = if x < SPLIT_COL {
+            (false, x)
+        } else {
+            (true, x - SPLIT_COL)
+        };
+        let shift = if col.is_multiple_of(2) { 4 } else { 0 };
+        Some((half, y * ROW_BYTES + col / 2, shift))
+    }
+}
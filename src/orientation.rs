@@ -0,0 +1,153 @@
+//! How logical coordinates map onto the glass.
+
+use crate::constants::{HEIGHT, WIDTH};
+
+/// How far the panel is turned clockwise from its normal landscape mounting
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Rotation {
+    /// Landscape, flex cable at the bottom
+    #[default]
+    Deg0,
+    /// Portrait, turned a quarter turn clockwise
+    Deg90,
+    /// Landscape, upside down
+    Deg180,
+    /// Portrait, turned a quarter turn counter-clockwise
+    Deg270,
+}
+
+/// Mounting of the panel, so frames can be drawn upright in any position
+///
+/// The display compensates for the [`Rotation`] and mirroring while packing
+/// the frame, so coordinates passed to [`InkyDisplay::set_pixel`] and the
+/// canvas are always upright from the viewer's point of view. Portrait
+/// rotations swap the logical width and height to 1200×1600.
+///
+/// Mirroring flips the logical frame before it is rotated, for panels viewed
+/// through a mirror or from behind.
+///
+/// [`InkyDisplay::set_pixel`]: crate::InkyDisplay::set_pixel
+///
+/// # Example
+/// ```
+/// use inky_el133::{Orientation, Rotation};
+///
+/// let portrait = Orientation::new(Rotation::Deg90);
+/// assert_eq!((portrait.width(), portrait.height()), (1200, 1600));
+///
+/// let mirrored = Orientation::LANDSCAPE.mirror_horizontal(true);
+/// assert_eq!((mirrored.width(), mirrored.height()), (1600, 1200));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Orientation {
+    rotation: Rotation,
+    mirror_horizontal: bool,
+    mirror_vertical: bool,
+}
+
+impl Orientation {
+    /// Normal landscape mounting, no mirroring
+    pub const LANDSCAPE: Orientation = Orientation::new(Rotation::Deg0);
+
+    /// Panel turned by `rotation`, without mirroring
+    pub const fn new(rotation: Rotation) -> Self {
+        Self {
+            rotation,
+            mirror_horizontal: false,
+            mirror_vertical: false,
+        }
+    }
+
+    /// Flip the frame left to right
+    pub const fn mirror_horizontal(mut self, mirror: bool) -> Self {
+        self.mirror_horizontal = mirror;
+        self
+    }
+
+    /// Flip the frame top to bottom
+    pub const fn mirror_vertical(mut self, mirror: bool) -> Self {
+        self.mirror_vertical = mirror;
+        self
+    }
+
+    /// Rotation of the panel
+    pub const fn rotation(&self) -> Rotation {
+        self.rotation
+    }
+
+    /// Whether the frame is flipped left to right
+    pub const fn is_mirrored_horizontally(&self) -> bool {
+        self.mirror_horizontal
+    }
+
+    /// Whether the frame is flipped top to bottom
+    pub const fn is_mirrored_vertically(&self) -> bool {
+        self.mirror_vertical
+    }
+
+    /// Logical width in pixels (1600, or 1200 in portrait)
+    pub const fn width(&self) -> usize {
+        match self.rotation {
+            Rotation::Deg0 | Rotation::Deg180 => WIDTH,
+            Rotation::Deg90 | Rotation::Deg270 => HEIGHT,
+        }
+    }
+
+    /// Logical height in pixels (1200, or 1600 in portrait)
+    pub const fn height(&self) -> usize {
+        match self.rotation {
+            Rotation::Deg0 | Rotation::Deg180 => HEIGHT,
+            Rotation::Deg90 | Rotation::Deg270 => WIDTH,
+        }
+    }
+
+    /// Native panel position `(row, col)` of logical pixel `(x, y)`
+    ///
+    /// The native frame is the landscape frame turned -90 degrees: native
+    /// row `WIDTH - 1 - px` holds landscape column `px`, and native column
+    /// `py` holds landscape row `py`.
+    #[cfg(feature = "emulator")]
+    pub(crate) const fn native_position(self, x: usize, y: usize) -> (usize, usize) {
+        let x = if self.mirror_horizontal {
+            self.width() - 1 - x
+        } else {
+            x
+        };
+        let y = if self.mirror_vertical {
+            self.height() - 1 - y
+        } else {
+            y
+        };
+
+        let (px, py) = match self.rotation {
+            Rotation::Deg0 => (x, y),
+            Rotation::Deg90 => (y, HEIGHT - 1 - x),
+            Rotation::Deg180 => (WIDTH - 1 - x, HEIGHT - 1 - y),
+            Rotation::Deg270 => (WIDTH - 1 - y, x),
+        };
+        (WIDTH - 1 - px, py)
+    }
+
+    /// Logical pixel `(x, y)` shown at native panel position `(row, col)`
+    pub(crate) const fn logical_position(self, row: usize, col: usize) -> (usize, usize) {
+        let (px, py) = (WIDTH - 1 - row, col);
+        let (x, y) = match self.rotation {
+            Rotation::Deg0 => (px, py),
+            Rotation::Deg90 => (HEIGHT - 1 - py, px),
+            Rotation::Deg180 => (WIDTH - 1 - px, HEIGHT - 1 - py),
+            Rotation::Deg270 => (py, WIDTH - 1 - px),
+        };
+
+        let x = if self.mirror_horizontal {
+            self.width() - 1 - x
+        } else {
+            x
+        };
+        let y = if self.mirror_vertical {
+            self.height() - 1 - y
+        } else {
+            y
+        };
+        (x, y)
+    }
+}
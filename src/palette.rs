@@ -0,0 +1,220 @@
+//! RGB values of the inks and matching arbitrary colors against them.
+
+use crate::Color;
+use crate::colorspace;
+
+/// How [`Palette::nearest`] measures the distance between two colors
+///
+/// The perceptual metrics use software floating-point functions, so every
+/// metric picks the same colors with or without `std` and on every platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Metric {
+    /// Squared Euclidean distance between sRGB values
+    Rgb,
+    /// Euclidean distance in CIELAB (CIE76)
+    Cielab,
+    /// CIEDE2000 difference in CIELAB
+    ///
+    /// The most faithful match, but an order of magnitude slower than
+    /// [`Oklab`](Self::Oklab) when converting whole frames.
+    Ciede2000,
+    /// Euclidean distance in OKLab
+    Oklab,
+}
+
+impl Metric {
+    /// [`Oklab`](Self::Oklab)
+    pub const DEFAULT: Metric = Metric::Oklab;
+}
+
+impl Default for Metric {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// RGB value for each of the six [`Color`]s, and the metric used to match
+/// against them
+///
+/// Every conversion from RGB to panel colors in this crate goes through a
+/// `Palette`, and every conversion back to RGB reads from one, so the same
+/// palette drives both quantization and previews. The default is
+/// [`Palette::MEASURED`].
+///
+/// # Example
+/// ```
+/// use inky_el133::{Color, Metric, Palette};
+///
+/// let palette = Palette::default();
+/// assert_eq!(palette.nearest([250, 10, 20]), Color::Red);
+/// assert_eq!(palette.rgb(Color::Blue), [61, 59, 94]);
+///
+/// // Calibrated values from your own panel
+/// let mut mine = Palette::MEASURED.with_metric(Metric::Rgb);
+/// mine.set_rgb(Color::White, [180, 182, 178]);
+/// assert_eq!(mine.nearest([200, 200, 200]), Color::White);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    /// Indexed like [`Color::ALL`]
+    rgb: [[u8; 3]; 6],
+    metric: Metric,
+}
+
+impl Palette {
+    /// Idealized primaries from [`Color::rgb`], matched in sRGB
+    pub const NOMINAL: Palette = Palette::new([
+        Color::Black.rgb(),
+        Color::White.rgb(),
+        Color::Yellow.rgb(),
+        Color::Red.rgb(),
+        Color::Blue.rgb(),
+        Color::Green.rgb(),
+    ])
+    .with_metric(Metric::Rgb);
+
+    /// Inks as photographed on a real Spectra 6 panel (values from Pimoroni's
+    /// Python library), matched with [`Metric::DEFAULT`]
+    pub const MEASURED: Palette = Palette::new([
+        [0, 0, 0],
+        [161, 164, 165],
+        [208, 190, 71],
+        [156, 72, 75],
+        [61, 59, 94],
+        [58, 91, 70],
+    ]);
+
+    /// Create a palette from RGB values listed in [`Color::ALL`] order,
+    /// matched with [`Metric::DEFAULT`]
+    pub const fn new(rgb: [[u8; 3]; 6]) -> Self {
+        Self {
+            rgb,
+            metric: Metric::DEFAULT,
+        }
+    }
+
+    /// Same palette, matched with `metric`
+    pub const fn with_metric(self, metric: Metric) -> Self {
+        Self { metric, ..self }
+    }
+
+    /// Metric used by [`nearest`](Self::nearest)
+    pub const fn metric(&self) -> Metric {
+        self.metric
+    }
+
+    /// RGB value of `color`
+    pub const fn rgb(&self, color: Color) -> [u8; 3] {
+        self.rgb[slot(color)]
+    }
+
+    /// Replace the RGB value of `color`
+    pub fn set_rgb(&mut self, color: Color, rgb: [u8; 3]) {
+        self.rgb[slot(color)] = rgb;
+    }
+
+    /// Color closest to `rgb` under the palette's [`Metric`]
+    ///
+    /// Each call converts the whole palette into the metric's color space.
+    /// The crate's own image loaders and dithers do that once per frame.
+    pub fn nearest(&self, rgb: [u8; 3]) -> Color {
+        self.matcher().nearest(rgb)
+    }
+
+    /// Matcher with the palette already converted for its metric
+    pub(crate) fn matcher(&self) -> Matcher {
+        Matcher::new(self)
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::MEASURED
+    }
+}
+
+/// Palette prepared for repeated [`nearest`](Matcher::nearest) lookups
+pub(crate) struct Matcher {
+    rgb: [[u8; 3]; 6],
+    perceptual: Option<Perceptual>,
+}
+
+/// Palette coordinates in a perceptual color space
+struct Perceptual {
+    metric: Metric,
+    linear: [f32; 256],
+    targets: [[f32; 3]; 6],
+}
+
+impl Perceptual {
+    fn new(palette: &Palette) -> Self {
+        let mut perceptual = Self {
+            metric: palette.metric,
+            linear: colorspace::linear_table(),
+            targets: [[0.0; 3]; 6],
+        };
+        perceptual.targets = palette.rgb.map(|rgb| perceptual.convert(rgb));
+        perceptual
+    }
+
+    fn convert(&self, rgb: [u8; 3]) -> [f32; 3] {
+        let linear = rgb.map(|c| self.linear[c as usize]);
+        match self.metric {
+            Metric::Oklab => colorspace::oklab(linear),
+            _ => colorspace::lab(linear),
+        }
+    }
+}
+
+impl Matcher {
+    fn new(palette: &Palette) -> Self {
+        Self {
+            rgb: palette.rgb,
+            perceptual: (palette.metric != Metric::Rgb).then(|| Perceptual::new(palette)),
+        }
+    }
+
+    /// Color closest to `rgb`
+    pub(crate) fn nearest(&self, rgb: [u8; 3]) -> Color {
+        if let Some(perceptual) = &self.perceptual {
+            let point = perceptual.convert(rgb);
+            let distance = |target: &[f32; 3]| match perceptual.metric {
+                Metric::Ciede2000 => colorspace::ciede2000(point, *target),
+                _ => colorspace::distance_squared(point, *target),
+            };
+            return closest(perceptual.targets.iter().map(distance));
+        }
+
+        closest(self.rgb.iter().map(|target| {
+            target
+                .iter()
+                .zip(rgb)
+                .map(|(&a, b)| (a as i32 - b as i32).pow(2))
+                .sum::<i32>()
+        }))
+    }
+}
+
+/// Color at the position of the smallest distance, in [`Color::ALL`] order
+fn closest<D: PartialOrd>(distances: impl Iterator<Item = D>) -> Color {
+    let mut best: Option<(Color, D)> = None;
+    for (color, distance) in Color::ALL.into_iter().zip(distances) {
+        if best.as_ref().is_none_or(|(_, d)| distance < *d) {
+            best = Some((color, distance));
+        }
+    }
+    best.map_or_else(Color::default, |(color, _)| color)
+}
+
+/// Position of `color` in [`Color::ALL`]
+const fn slot(color: Color) -> usize {
+    match color {
+        Color::Black => 0,
+        Color::White => 1,
+        Color::Yellow => 2,
+        Color::Red => 3,
+        Color::Blue => 4,
+        Color::Green => 5,
+    }
+}
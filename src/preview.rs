@@ -0,0 +1,136 @@
+//! Rendering a frame the way it will look on the glass.
+
+use alloc::vec::Vec;
+
+use crate::{Canvas, Color, Palette};
+
+/// Renders a [`Canvas`] to RGB using the panel's real appearance
+///
+/// Inks come from a [`Palette`] of measured colors rather than the idealized
+/// primaries, so a preview shows the dull, low-contrast result the panel
+/// actually produces. Each panel pixel can be enlarged into a cell with the
+/// paper showing through a grid between cells, and the lighting and glare of
+/// the room can be simulated. Nothing here touches hardware.
+///
+/// # Example
+/// ```
+/// use inky_el133::{Canvas, Color, Palette, Preview};
+///
+/// let mut canvas = Canvas::new();
+/// canvas.set_pixel(0, 0, Color::Red)?;
+///
+/// let preview = Preview::new(Palette::MEASURED).scale(3).grid(true);
+/// assert_eq!(preview.size(&canvas), (4800, 3600));
+///
+/// let rgb = preview.render(&canvas);
+/// assert_eq!(rgb[..3], Palette::MEASURED.rgb(Color::Red));
+/// // The third column of the cell is a grid line
+/// assert_eq!(rgb[6..9], Palette::MEASURED.rgb(Color::White));
+/// # Ok::<(), inky_el133::InkyError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Preview {
+    palette: Palette,
+    paper: [u8; 3],
+    scale: usize,
+    grid: bool,
+    lighting: u8,
+    glare: u8,
+}
+
+impl Preview {
+    /// Render with the inks in `palette`, one RGB pixel per panel pixel
+    ///
+    /// The paper tone starts as the palette's white.
+    pub const fn new(palette: Palette) -> Self {
+        Self {
+            palette,
+            paper: palette.rgb(Color::White),
+            scale: 1,
+            grid: false,
+            lighting: u8::MAX,
+            glare: 0,
+        }
+    }
+
+    /// Tone of the bare paper showing through the pixel grid
+    pub const fn paper(mut self, rgb: [u8; 3]) -> Self {
+        self.paper = rgb;
+        self
+    }
+
+    /// Draw each panel pixel as a `scale`×`scale` cell (default 1, minimum 1)
+    pub const fn scale(mut self, scale: usize) -> Self {
+        self.scale = if scale == 0 { 1 } else { scale };
+        self
+    }
+
+    /// Show the paper as a one-pixel grid between cells (default `false`)
+    ///
+    /// Only visible with a [`scale`](Self::scale) of 2 or more.
+    pub const fn grid(mut self, grid: bool) -> Self {
+        self.grid = grid;
+        self
+    }
+
+    /// Ambient light reflected by the inks, 255 being the light the palette
+    /// was measured under (default 255)
+    pub const fn lighting(mut self, level: u8) -> Self {
+        self.lighting = level;
+        self
+    }
+
+    /// Strength of the reflection off the front glass, brightest in the top
+    /// left corner and fading towards the bottom right (default 0, none)
+    pub const fn glare(mut self, level: u8) -> Self {
+        self.glare = level;
+        self
+    }
+
+    /// Width and height in pixels of the image [`render`](Self::render) produces
+    pub fn size(&self, canvas: &Canvas) -> (usize, usize) {
+        (canvas.width() * self.scale, canvas.height() * self.scale)
+    }
+
+    /// Render `canvas` as packed RGB8, three bytes per pixel, row-major
+    pub fn render(&self, canvas: &Canvas) -> Vec<u8> {
+        let (width, height) = self.size(canvas);
+        let lit = |rgb: [u8; 3]| rgb.map(|c| (c as u32 * self.lighting as u32 / 255) as u8);
+        let paper = lit(self.paper);
+        let grid = self.grid && self.scale > 1;
+
+        // Indexed by color index
+        let mut inks = [[0; 3]; 8];
+        for color in Color::ALL {
+            inks[color.index() as usize] = lit(self.palette.rgb(color));
+        }
+
+        let mut rgb = Vec::with_capacity(width * height * 3);
+        let rows = canvas
+            .rows()
+            .flat_map(|row| core::iter::repeat_n(row, self.scale));
+        for (y, row) in rows.enumerate() {
+            let gap_row = grid && y % self.scale == self.scale - 1;
+            for x in 0..width {
+                let gap = gap_row || (grid && x % self.scale == self.scale - 1);
+                let base = if gap {
+                    paper
+                } else {
+                    inks[row[x / self.scale].index() as usize]
+                };
+
+                // Screen-blend the glare, fading linearly along the diagonal
+                let glare =
+                    self.glare as u32 * (width + height - x - y) as u32 / (width + height) as u32;
+                rgb.extend(base.map(|c| c + ((255 - c) as u32 * glare / 255) as u8));
+            }
+        }
+        rgb
+    }
+}
+
+impl Default for Preview {
+    fn default() -> Self {
+        Self::new(Palette::default())
+    }
+}
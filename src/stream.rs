@@ -0,0 +1,66 @@
+//! Allocation-free generation of the packed CS0/CS1 frame data.
+//!
+//! Produces exactly the bytes `Canvas::rotate_and_split_into` would, one
+//! native panel row at a time, so a frame can go out over SPI without a
+//! rotated copy or any heap buffers.
+
+use crate::constants::*;
+use crate::error::Result;
+use crate::transport::{ChipSelect, Transport};
+use crate::{Color, Orientation};
+
+/// Pack one native row of a controller's half from a logical pixel source.
+///
+/// `orientation` gives the logical pixel shown at each native position; in
+/// landscape, native row `row` is logical column `WIDTH - 1 - row` and native
+/// column `col` is logical row `col`.
+fn pack_row<F>(
+    orientation: Orientation,
+    first_col: usize,
+    row: usize,
+    pixel: &mut F,
+    out: &mut [u8; ROW_BYTES],
+) -> Result<()>
+where
+    F: FnMut(usize, usize) -> Result<Color>,
+{
+    let mut at = |col| {
+        let (x, y) = orientation.logical_position(row, col);
+        pixel(x, y).map(Color::index)
+    };
+    for (i, byte) in out.iter_mut().enumerate() {
+        let col = first_col + i * 2;
+        let first = at(col)?;
+        let second = at(col + 1)?;
+        *byte = (first << 4) | second;
+    }
+    Ok(())
+}
+
+/// Send one controller's `CMD_DTM` frame, generated row by row from `pixel`.
+///
+/// `cs` must be [`ChipSelect::CS0`] or [`ChipSelect::CS1`].
+pub(crate) fn send_half<T, F>(
+    transport: &mut T,
+    cs: ChipSelect,
+    orientation: Orientation,
+    pixel: &mut F,
+) -> Result<()>
+where
+    T: Transport,
+    F: FnMut(usize, usize) -> Result<Color>,
+{
+    debug_assert!(cs != ChipSelect::Both, "frame data goes to one controller");
+    let first_col = if cs == ChipSelect::CS1 { SPLIT_COL } else { 0 };
+
+    let mut row = [0u8; ROW_BYTES];
+    transport.begin_command(cs, CMD_DTM)?;
+    for r in 0..WIDTH {
+        if let Err(err) = pack_row(orientation, first_col, r, pixel, &mut row) {
+            transport.end_command()?;
+            return Err(err);
+        }
+        transport.write_data(&row)?;
+    }
+    transport.end_command()
+}
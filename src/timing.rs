@@ -0,0 +1,113 @@
+//! Command delays and busy-wait timeouts.
+
+use core::time::Duration;
+
+/// Timing profile used by [`InkyDisplay`](crate::InkyDisplay)
+///
+/// The default profile only waits where the panel asks for it through the
+/// BUSY line. [`Timing::conservative`] reproduces the Python driver, which
+/// sleeps 300 ms before every command and adds roughly five seconds to
+/// initialization.
+///
+/// # Example
+/// ```no_run
+/// # #[cfg(feature = "std")] {
+/// use inky_el133::{InkyDisplay, Timing};
+///
+/// let display = InkyDisplay::builder()
+///     .timing(Timing::conservative())
+///     .build()?;
+/// # }
+/// # Ok::<(), inky_el133::InkyError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    /// Delay before every command byte
+    pub command_delay_ms: u64,
+    /// BUSY timeout after reset, before the init sequence
+    pub init_timeout_ms: u64,
+    /// BUSY timeout after `CMD_PON`
+    pub power_on_timeout_ms: u64,
+    /// BUSY timeout after `CMD_DRF`; a full refresh takes about 32 seconds
+    pub refresh_timeout_ms: u64,
+    /// BUSY timeout after `CMD_POF`
+    pub power_off_timeout_ms: u64,
+}
+
+impl Timing {
+    /// Only wait on the BUSY line; no fixed per-command delay
+    pub const fn fast() -> Self {
+        Self {
+            command_delay_ms: 0,
+            init_timeout_ms: 300,
+            power_on_timeout_ms: 200,
+            refresh_timeout_ms: 32_000,
+            power_off_timeout_ms: 200,
+        }
+    }
+
+    /// Timing of the reference Python driver (inky_el133uf1.py:384)
+    pub const fn conservative() -> Self {
+        Self {
+            command_delay_ms: 300,
+            ..Self::fast()
+        }
+    }
+}
+
+impl Timing {
+    /// BUSY timeout after the command that starts `phase`
+    pub(crate) const fn timeout_ms(&self, phase: Phase) -> u64 {
+        match phase {
+            Phase::Init => self.init_timeout_ms,
+            Phase::PowerOn => self.power_on_timeout_ms,
+            Phase::Refresh => self.refresh_timeout_ms,
+            Phase::PowerOff => self.power_off_timeout_ms,
+        }
+    }
+}
+
+impl Default for Timing {
+    fn default() -> Self {
+        Self::fast()
+    }
+}
+
+/// Stage of talking to the panel that waits on the BUSY line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// After reset, before the init sequence
+    Init,
+    /// After `CMD_PON`
+    PowerOn,
+    /// After `CMD_DRF`
+    Refresh,
+    /// After `CMD_POF`
+    PowerOff,
+}
+
+impl core::fmt::Display for Phase {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Phase::Init => "init",
+            Phase::PowerOn => "power on",
+            Phase::Refresh => "refresh",
+            Phase::PowerOff => "power off",
+        })
+    }
+}
+
+/// How long the panel held BUSY during each phase of the last refresh
+///
+/// Precision depends on the transport: the Linux controller wakes on the BUSY
+/// edge and reads the clock then, while polling transports round up to their
+/// poll interval.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BusyTimes {
+    /// After `CMD_PON`
+    pub power_on: Duration,
+    /// After `CMD_DRF`
+    pub refresh: Duration,
+    /// After `CMD_POF`
+    pub power_off: Duration,
+}
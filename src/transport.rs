@@ -0,0 +1,246 @@
+//! Transport abstraction between [`InkyDisplay`](crate::InkyDisplay) and the
+//! panel hardware.
+//!
+//! The display logic only needs three operations from the hardware: a reset
+//! pulse, writing a command (with optional data) to one or both controllers,
+//! and waiting for the BUSY line. Implementing [`Transport`] lets the same
+//! init and refresh sequence run against real GPIO/SPI or an in-memory fake.
+//!
+//! Commands are written in three steps ([`Transport::begin_command`],
+//! [`Transport::write_data`], [`Transport::end_command`]) so that frame data
+//! can be streamed to the panel without ever holding a whole frame in memory.
+
+#[cfg(feature = "alloc")]
+use alloc::collections::VecDeque;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use core::time::Duration;
+
+use crate::error::Result;
+
+/// Chip select options for dual-controller display
+///
+/// The EL133UF1 is driven by two cascaded controllers: CS0 owns the left half
+/// of the panel (in native orientation) and CS1 the right half.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChipSelect {
+    CS0,
+    CS1,
+    Both,
+}
+
+/// Low-level hardware access used by [`InkyDisplay`](crate::InkyDisplay).
+pub trait Transport {
+    /// Perform a hardware reset of the display
+    fn reset(&mut self) -> Result<()>;
+
+    /// Drive the RESET line; `true` holds the controllers in reset
+    ///
+    /// Lets the caller time the reset pulse itself, as the async show does
+    /// on the Tokio timer. The default performs a whole blocking
+    /// [`reset`](Self::reset) when the line is asserted and nothing on
+    /// release.
+    fn set_reset(&mut self, asserted: bool) -> Result<()> {
+        if asserted { self.reset() } else { Ok(()) }
+    }
+
+    /// Select the controller(s) and send a command byte
+    fn begin_command(&mut self, cs: ChipSelect, cmd: u8) -> Result<()>;
+
+    /// Send data bytes for the current command; may be called repeatedly
+    fn write_data(&mut self, data: &[u8]) -> Result<()>;
+
+    /// Finish the current command and deselect the controller(s)
+    fn end_command(&mut self) -> Result<()>;
+
+    /// Send a command byte followed by optional data to the selected controller(s)
+    fn send_command(&mut self, cs: ChipSelect, cmd: u8, data: &[u8]) -> Result<()> {
+        self.begin_command(cs, cmd)?;
+        if !data.is_empty() {
+            self.write_data(data)?;
+        }
+        self.end_command()
+    }
+
+    /// Wait for the display to report ready, giving up after `timeout_ms`
+    ///
+    /// Returns how long the display stayed busy, or `None` if it was still
+    /// busy when the timeout expired.
+    fn wait_busy(&mut self, timeout_ms: u64) -> Result<Option<Duration>>;
+
+    /// Sample the BUSY line without waiting
+    fn is_busy(&mut self) -> Result<bool>;
+
+    /// Block for `ms` milliseconds
+    fn delay_ms(&mut self, ms: u64);
+}
+
+/// A single operation captured by [`RecordingTransport`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    Reset,
+    Command {
+        cs: ChipSelect,
+        cmd: u8,
+        data: Vec<u8>,
+    },
+    WaitBusy {
+        timeout_ms: u64,
+    },
+    Delay {
+        ms: u64,
+    },
+}
+
+/// In-memory transport that records every operation instead of touching hardware.
+///
+/// Useful for exercising [`InkyDisplay`](crate::InkyDisplay) on machines
+/// without a panel attached. BUSY releases at once unless scripted otherwise
+/// with [`push_wait`](Self::push_wait) or [`set_busy`](Self::set_busy).
+///
+/// # Example
+/// ```
+/// use inky_el133::{InkyDisplay, colors};
+/// use inky_el133::transport::{ChipSelect, RecordingTransport};
+///
+/// let mut display = InkyDisplay::with_transport(RecordingTransport::new())?;
+/// display.fill(colors::RED);
+/// display.show()?;
+///
+/// let frame = display.transport().command_data(ChipSelect::CS0, 0x10).unwrap(); // CMD_DTM
+/// assert_eq!(frame.len(), 600 * 1600 / 2);
+/// assert!(frame.iter().all(|&b| b == 0x33));
+/// # Ok::<(), inky_el133::InkyError>(())
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Default, Clone)]
+pub struct RecordingTransport {
+    events: Vec<Event>,
+    /// Scripted results for upcoming `wait_busy` calls
+    waits: VecDeque<Option<Duration>>,
+    busy: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl RecordingTransport {
+    /// Create an empty recording transport
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All operations recorded so far, oldest first
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Iterate over the recorded commands as `(cs, cmd, data)`
+    pub fn commands(&self) -> impl Iterator<Item = (ChipSelect, u8, &[u8])> {
+        self.events.iter().filter_map(|event| match event {
+            Event::Command { cs, cmd, data } => Some((*cs, *cmd, data.as_slice())),
+            _ => None,
+        })
+    }
+
+    /// Data of the most recent `cmd` sent to exactly `cs`
+    pub fn command_data(&self, cs: ChipSelect, cmd: u8) -> Option<&[u8]> {
+        self.commands()
+            .filter(|&(c, command, _)| c == cs && command == cmd)
+            .last()
+            .map(|(_, _, data)| data)
+    }
+
+    /// Forget all recorded operations
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    /// Script the result of an upcoming [`Transport::wait_busy`] call
+    ///
+    /// Results are used in the order they were pushed, one per call; `None`
+    /// is a timeout. Once the queue is empty, waits follow
+    /// [`set_busy`](Self::set_busy).
+    ///
+    /// # Example
+    /// ```
+    /// use core::time::Duration;
+    /// use inky_el133::{InkyDisplay, InkyError, Phase};
+    /// use inky_el133::transport::RecordingTransport;
+    ///
+    /// let mut transport = RecordingTransport::new();
+    /// transport.push_wait(Some(Duration::ZERO)); // init
+    /// transport.push_wait(None); // power on times out
+    ///
+    /// let mut display = InkyDisplay::attach(transport);
+    /// display.set_strict(true);
+    /// let result = display.show();
+    /// assert!(matches!(result, Err(InkyError::BusyTimeout(Phase::PowerOn))));
+    /// ```
+    pub fn push_wait(&mut self, result: Option<Duration>) {
+        self.waits.push_back(result);
+    }
+
+    /// Hold BUSY high, as the line does with no panel attached
+    ///
+    /// While set, [`Transport::is_busy`] reports busy and waits that have no
+    /// scripted result time out.
+    ///
+    /// # Example
+    /// ```
+    /// use inky_el133::{InkyDisplay, InkyError};
+    /// use inky_el133::transport::RecordingTransport;
+    ///
+    /// let mut transport = RecordingTransport::new();
+    /// transport.set_busy(true);
+    ///
+    /// let mut display = InkyDisplay::attach(transport);
+    /// display.set_strict(true);
+    /// assert!(matches!(display.initialize(), Err(InkyError::NotConnected)));
+    /// ```
+    pub fn set_busy(&mut self, busy: bool) {
+        self.busy = busy;
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Transport for RecordingTransport {
+    fn reset(&mut self) -> Result<()> {
+        self.events.push(Event::Reset);
+        Ok(())
+    }
+
+    fn begin_command(&mut self, cs: ChipSelect, cmd: u8) -> Result<()> {
+        self.events.push(Event::Command {
+            cs,
+            cmd,
+            data: Vec::new(),
+        });
+        Ok(())
+    }
+
+    fn write_data(&mut self, bytes: &[u8]) -> Result<()> {
+        if let Some(Event::Command { data, .. }) = self.events.last_mut() {
+            data.extend_from_slice(bytes);
+        }
+        Ok(())
+    }
+
+    fn end_command(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn wait_busy(&mut self, timeout_ms: u64) -> Result<Option<Duration>> {
+        self.events.push(Event::WaitBusy { timeout_ms });
+        let released = (!self.busy).then_some(Duration::ZERO);
+        Ok(self.waits.pop_front().unwrap_or(released))
+    }
+
+    fn is_busy(&mut self) -> Result<bool> {
+        Ok(self.busy)
+    }
+
+    fn delay_ms(&mut self, ms: u64) {
+        self.events.push(Event::Delay { ms });
+    }
+}